@@ -0,0 +1,66 @@
+//! Throughput of a full bit flip sweep over a key-sized input: copying mutators (extract, flip,
+//! include) against the in-place `BitFlipMutator`
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use metamorphic_testing_rs::{BitFlipMutator, BitInclusionMutator, Mutator};
+
+/// ML-KEM-768 secret key size
+const INPUT_SIZE: usize = 2400;
+
+fn sweep_copy<I: Clone>(mutator: &impl Mutator<I, ()>, input: &I) {
+    for element in 0..INPUT_SIZE * 8 {
+        black_box(mutator.mutate_input(input, &(), element));
+    }
+}
+
+fn sweep_in_place<I: Clone>(mutator: &impl Mutator<I, ()>, input: &I) {
+    let mut working_input = input.clone();
+    for element in 0..INPUT_SIZE * 8 {
+        mutator.mutate_in_place(&mut working_input, element);
+        black_box(&working_input);
+        mutator.undo_in_place(&mut working_input, element);
+    }
+}
+
+fn bit_flip_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Bit flip sweep");
+    group.throughput(Throughput::Elements((INPUT_SIZE * 8) as u64));
+
+    let vec_input = vec![0x5au8; INPUT_SIZE];
+    let vec_copy = BitInclusionMutator::new(
+        |input: &Vec<u8>| input.clone(),
+        |_state: &(), _input: &Vec<u8>, mutated| ((), mutated),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("copy", "Vec<u8>"),
+        &vec_input,
+        |b, input| b.iter(|| sweep_copy(&vec_copy, input)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("in place", "Vec<u8>"),
+        &vec_input,
+        |b, input| b.iter(|| sweep_in_place(&BitFlipMutator::whole(), input)),
+    );
+
+    let array_input = [0x5au8; INPUT_SIZE];
+    let array_copy = BitInclusionMutator::new(
+        |input: &[u8; INPUT_SIZE]| input.to_vec(),
+        |_state: &(), _input: &[u8; INPUT_SIZE], mutated: Vec<u8>| {
+            ((), mutated.try_into().unwrap())
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("copy", "[u8; 2400]"),
+        &array_input,
+        |b, input| b.iter(|| sweep_copy(&array_copy, input)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("in place", "[u8; 2400]"),
+        &array_input,
+        |b, input| b.iter(|| sweep_in_place(&BitFlipMutator::whole(), input)),
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, bit_flip_sweep);
+criterion_main!(benches);
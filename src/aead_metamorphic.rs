@@ -0,0 +1,184 @@
+use crate::AeadMetamorphic;
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+
+macro_rules! impl_aead_metamorphic {
+    ($aead_type:ty, $struct_name:ident, $libname:expr, $keysize:expr, $noncesize:expr) => {
+        pub struct $struct_name {}
+        impl AeadMetamorphic for $struct_name {
+            const LIBNAME: &str = $libname;
+            const KEYSIZE: usize = $keysize;
+            const NONCESIZE: usize = $noncesize;
+            const TAGSIZE: usize = 16;
+
+            fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+                let mut buffer = plaintext.to_vec();
+                <$aead_type>::new_from_slice(key)
+                    .unwrap()
+                    .encrypt_in_place(GenericArray::from_slice(nonce), aad, &mut buffer)
+                    .unwrap();
+                buffer
+            }
+
+            fn seal_detached(
+                key: &[u8],
+                nonce: &[u8],
+                aad: &[u8],
+                plaintext: &[u8],
+            ) -> (Vec<u8>, Vec<u8>) {
+                let mut buffer = plaintext.to_vec();
+                let tag = <$aead_type>::new_from_slice(key)
+                    .unwrap()
+                    .encrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer)
+                    .unwrap();
+                (buffer, tag.to_vec())
+            }
+
+            fn open(key: &[u8], nonce: &[u8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+                let mut buffer = sealed.to_vec();
+                <$aead_type>::new_from_slice(key)
+                    .unwrap()
+                    .decrypt_in_place(GenericArray::from_slice(nonce), aad, &mut buffer)
+                    .ok()?;
+                Some(buffer)
+            }
+
+            fn open_detached(
+                key: &[u8],
+                nonce: &[u8],
+                aad: &[u8],
+                ciphertext: &[u8],
+                tag: &[u8],
+            ) -> Option<Vec<u8>> {
+                let mut buffer = ciphertext.to_vec();
+                <$aead_type>::new_from_slice(key)
+                    .unwrap()
+                    .decrypt_in_place_detached(
+                        GenericArray::from_slice(nonce),
+                        aad,
+                        &mut buffer,
+                        GenericArray::from_slice(tag),
+                    )
+                    .ok()?;
+                Some(buffer)
+            }
+        }
+    };
+}
+
+impl_aead_metamorphic!(Aes128Gcm, Aes128GcmMetamorphic, "AES-128-GCM", 16, 12);
+impl_aead_metamorphic!(Aes256Gcm, Aes256GcmMetamorphic, "AES-256-GCM", 32, 12);
+impl_aead_metamorphic!(
+    ChaCha20Poly1305,
+    ChaCha20Poly1305Metamorphic,
+    "ChaCha20-Poly1305",
+    32,
+    12
+);
+impl_aead_metamorphic!(
+    XChaCha20Poly1305,
+    XChaCha20Poly1305Metamorphic,
+    "XChaCha20-Poly1305",
+    32,
+    24
+);
+
+macro_rules! impl_aead_metamorphic_ring {
+    ($algorithm:expr, $struct_name:ident, $libname:expr, $keysize:expr) => {
+        pub struct $struct_name {}
+        impl $struct_name {
+            fn key(key: &[u8]) -> ring::aead::LessSafeKey {
+                ring::aead::LessSafeKey::new(ring::aead::UnboundKey::new(&$algorithm, key).unwrap())
+            }
+
+            fn nonce(nonce: &[u8]) -> ring::aead::Nonce {
+                ring::aead::Nonce::try_assume_unique_for_key(nonce).unwrap()
+            }
+        }
+
+        impl AeadMetamorphic for $struct_name {
+            const LIBNAME: &str = $libname;
+            const KEYSIZE: usize = $keysize;
+            const NONCESIZE: usize = ring::aead::NONCE_LEN;
+            const TAGSIZE: usize = 16;
+
+            fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+                let mut buffer = plaintext.to_vec();
+                Self::key(key)
+                    .seal_in_place_append_tag(
+                        Self::nonce(nonce),
+                        ring::aead::Aad::from(aad),
+                        &mut buffer,
+                    )
+                    .unwrap();
+                buffer
+            }
+
+            fn seal_detached(
+                key: &[u8],
+                nonce: &[u8],
+                aad: &[u8],
+                plaintext: &[u8],
+            ) -> (Vec<u8>, Vec<u8>) {
+                let mut buffer = plaintext.to_vec();
+                let tag = Self::key(key)
+                    .seal_in_place_separate_tag(
+                        Self::nonce(nonce),
+                        ring::aead::Aad::from(aad),
+                        &mut buffer,
+                    )
+                    .unwrap();
+                (buffer, tag.as_ref().to_vec())
+            }
+
+            fn open(key: &[u8], nonce: &[u8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+                let mut buffer = sealed.to_vec();
+                let plaintext = Self::key(key)
+                    .open_in_place(Self::nonce(nonce), ring::aead::Aad::from(aad), &mut buffer)
+                    .ok()?;
+                Some(plaintext.to_vec())
+            }
+
+            fn open_detached(
+                key: &[u8],
+                nonce: &[u8],
+                aad: &[u8],
+                ciphertext: &[u8],
+                tag: &[u8],
+            ) -> Option<Vec<u8>> {
+                let mut buffer = ciphertext.to_vec();
+                let plaintext = Self::key(key)
+                    .open_in_place_separate_tag(
+                        Self::nonce(nonce),
+                        ring::aead::Aad::from(aad),
+                        ring::aead::Tag::try_from(tag).ok()?,
+                        &mut buffer,
+                        0..,
+                    )
+                    .ok()?;
+                Some(plaintext.to_vec())
+            }
+        }
+    };
+}
+
+impl_aead_metamorphic_ring!(
+    ring::aead::AES_128_GCM,
+    RingAes128GcmMetamorphic,
+    "Ring AES-128-GCM",
+    16
+);
+impl_aead_metamorphic_ring!(
+    ring::aead::AES_256_GCM,
+    RingAes256GcmMetamorphic,
+    "Ring AES-256-GCM",
+    32
+);
+impl_aead_metamorphic_ring!(
+    ring::aead::CHACHA20_POLY1305,
+    RingChaCha20Poly1305Metamorphic,
+    "Ring ChaCha20-Poly1305",
+    32
+);
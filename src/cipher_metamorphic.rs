@@ -0,0 +1,131 @@
+use crate::CipherMetamorphic;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{
+    BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit, StreamCipher, StreamCipherSeek,
+};
+use aes::{Aes128, Aes256};
+use chacha20::ChaCha20;
+use ctr::Ctr128BE;
+
+macro_rules! impl_cipher_metamorphic_stream {
+    ($cipher_type:ty, $struct_name:ident, $libname:expr, $keysize:expr, $ivsize:expr) => {
+        pub struct $struct_name {}
+        impl CipherMetamorphic for $struct_name {
+            type Encryptor = $cipher_type;
+
+            const LIBNAME: &str = $libname;
+            const KEYSIZE: usize = $keysize;
+            const IVSIZE: usize = $ivsize;
+            const BLOCKSIZE: usize = 1;
+            const STREAM: bool = true;
+
+            fn new_encryptor(key: &[u8], iv: &[u8]) -> Self::Encryptor {
+                <$cipher_type>::new_from_slices(key, iv).unwrap()
+            }
+
+            fn encrypt_update(encryptor: &mut Self::Encryptor, data: &mut [u8]) {
+                encryptor.apply_keystream(data);
+            }
+
+            fn decrypt(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+                Self::encrypt(key, iv, ciphertext)
+            }
+
+            fn seek(encryptor: &mut Self::Encryptor, offset: usize) -> bool {
+                encryptor.seek(offset as u64);
+                true
+            }
+        }
+    };
+}
+
+impl_cipher_metamorphic_stream!(
+    Ctr128BE<Aes128>,
+    Aes128CtrMetamorphic,
+    "AES-128-CTR",
+    16,
+    16
+);
+impl_cipher_metamorphic_stream!(
+    Ctr128BE<Aes256>,
+    Aes256CtrMetamorphic,
+    "AES-256-CTR",
+    32,
+    16
+);
+impl_cipher_metamorphic_stream!(ChaCha20, ChaCha20Metamorphic, "ChaCha20", 32, 12);
+
+macro_rules! impl_cipher_metamorphic_cbc {
+    ($block_cipher:ty, $struct_name:ident, $libname:expr, $keysize:expr) => {
+        pub struct $struct_name {}
+        impl CipherMetamorphic for $struct_name {
+            type Encryptor = cbc::Encryptor<$block_cipher>;
+
+            const LIBNAME: &str = $libname;
+            const KEYSIZE: usize = $keysize;
+            const IVSIZE: usize = 16;
+            const BLOCKSIZE: usize = 16;
+            const STREAM: bool = false;
+
+            fn new_encryptor(key: &[u8], iv: &[u8]) -> Self::Encryptor {
+                cbc::Encryptor::<$block_cipher>::new_from_slices(key, iv).unwrap()
+            }
+
+            fn encrypt_update(encryptor: &mut Self::Encryptor, data: &mut [u8]) {
+                for block in data.chunks_exact_mut(Self::BLOCKSIZE) {
+                    encryptor.encrypt_block_mut(GenericArray::from_mut_slice(block));
+                }
+            }
+
+            fn decrypt(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+                let mut decryptor =
+                    cbc::Decryptor::<$block_cipher>::new_from_slices(key, iv).unwrap();
+                let mut buffer = ciphertext.to_vec();
+                for block in buffer.chunks_exact_mut(Self::BLOCKSIZE) {
+                    decryptor.decrypt_block_mut(GenericArray::from_mut_slice(block));
+                }
+                buffer
+            }
+        }
+    };
+}
+
+impl_cipher_metamorphic_cbc!(Aes128, Aes128CbcMetamorphic, "AES-128-CBC", 16);
+impl_cipher_metamorphic_cbc!(Aes256, Aes256CbcMetamorphic, "AES-256-CBC", 32);
+
+macro_rules! impl_cipher_metamorphic_ecb {
+    ($block_cipher:ty, $struct_name:ident, $libname:expr, $keysize:expr) => {
+        pub struct $struct_name {}
+        impl CipherMetamorphic for $struct_name {
+            type Encryptor = ecb::Encryptor<$block_cipher>;
+
+            const LIBNAME: &str = $libname;
+            const KEYSIZE: usize = $keysize;
+            const IVSIZE: usize = 0;
+            const BLOCKSIZE: usize = 16;
+            const STREAM: bool = false;
+
+            fn new_encryptor(key: &[u8], _iv: &[u8]) -> Self::Encryptor {
+                ecb::Encryptor::<$block_cipher>::new_from_slice(key).unwrap()
+            }
+
+            fn encrypt_update(encryptor: &mut Self::Encryptor, data: &mut [u8]) {
+                for block in data.chunks_exact_mut(Self::BLOCKSIZE) {
+                    encryptor.encrypt_block_mut(GenericArray::from_mut_slice(block));
+                }
+            }
+
+            fn decrypt(key: &[u8], _iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+                let mut decryptor = ecb::Decryptor::<$block_cipher>::new_from_slice(key).unwrap();
+                let mut buffer = ciphertext.to_vec();
+                for block in buffer.chunks_exact_mut(Self::BLOCKSIZE) {
+                    decryptor.decrypt_block_mut(GenericArray::from_mut_slice(block));
+                }
+                buffer
+            }
+        }
+    };
+}
+
+impl_cipher_metamorphic_ecb!(Aes128, Aes128EcbMetamorphic, "AES-128-ECB", 16);
+impl_cipher_metamorphic_ecb!(Aes256, Aes256EcbMetamorphic, "AES-256-ECB", 32);
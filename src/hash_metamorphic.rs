@@ -1,7 +1,13 @@
-use crate::HashMetamorphic;
+use crate::{
+    campaign_rng, configured_hash_test, BitFlipMutator, BlockHash, CampaignOptions,
+    HashMetamorphic, IndexMutator, MetamorphicRelation, MetamorphicTarget, Relation,
+};
 use ascon_hash::AsconHash;
 use belt_hash::BeltHash;
-use blake2::{Blake2b512, Blake2s256};
+use blake2::{
+    digest::{Mac, VariableOutput},
+    Blake2b, Blake2b512, Blake2bMac512, Blake2bVar, Blake2s256,
+};
 use blake3::Hasher;
 use fsb::{Fsb160, Fsb224, Fsb256, Fsb384, Fsb512};
 use groestl::{Groestl224, Groestl256, Groestl384, Groestl512};
@@ -11,13 +17,22 @@ use rand::RngCore;
 use ring::digest::Context;
 use ripemd::{Ripemd128, Ripemd160, Ripemd256, Ripemd320};
 use sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+use sha3::digest::{core_api::BlockSizeUser, typenum::Unsigned};
 use sha3::{Digest, Sha3_224, Sha3_256, Sha3_384, Sha3_512};
 use shabal::{Shabal192, Shabal224, Shabal256, Shabal384, Shabal512};
-use skein::{consts::U32, Skein1024, Skein256, Skein512};
+use skein::{
+    consts::{U32, U64},
+    digest::generic_array::ArrayLength,
+    Skein1024, Skein256, Skein512,
+};
 use sm3::Sm3;
+use std::io::Write;
 use tiger::Tiger;
 use whirlpool::Whirlpool;
 
+/// Data absorbed before a reset, must not leak into the next digest
+const API_PATHS_GARBAGE: &[u8] = b"metamorphic-testing-rs";
+
 macro_rules! impl_hash_metamorphic {
     ($hash_type:ty, $test_struct_name:ident, $libname:literal) => {
         pub struct $test_struct_name {}
@@ -30,7 +45,7 @@ macro_rules! impl_hash_metamorphic {
 
             fn gen_input(size: usize) -> Self::Input {
                 let mut data = vec![0u8; size];
-                rand::thread_rng().fill_bytes(&mut data);
+                campaign_rng().fill_bytes(&mut data);
                 data
             }
 
@@ -76,6 +91,44 @@ macro_rules! impl_hash_metamorphic {
                 state.update(first_part);
                 (state, second_part.to_vec())
             }
+
+            fn hash_api_paths(message: &[u8]) -> Vec<Vec<u8>> {
+                let mut paths = vec![<$hash_type as Digest>::digest(message).to_vec()];
+                paths.push(
+                    <$hash_type>::new()
+                        .chain_update(message)
+                        .finalize()
+                        .to_vec(),
+                );
+
+                let mut output = Default::default();
+                Digest::finalize_into(<$hash_type>::new_with_prefix(message), &mut output);
+                paths.push(output.to_vec());
+
+                // Reuse a hasher after finalize_reset, twice
+                let mut hasher = <$hash_type>::new();
+                Digest::update(&mut hasher, API_PATHS_GARBAGE);
+                Digest::finalize_reset(&mut hasher);
+                Digest::update(&mut hasher, message);
+                paths.push(Digest::finalize_reset(&mut hasher).to_vec());
+                Digest::update(&mut hasher, message);
+                paths.push(Digest::finalize_reset(&mut hasher).to_vec());
+
+                let mut hasher = <$hash_type>::new();
+                Digest::update(&mut hasher, API_PATHS_GARBAGE);
+                Digest::reset(&mut hasher);
+                Digest::update(&mut hasher, message);
+                paths.push(hasher.finalize().to_vec());
+
+                let mut hasher = <$hash_type>::new();
+                hasher.write_all(message).unwrap();
+                paths.push(hasher.finalize().to_vec());
+                paths
+            }
+        }
+
+        impl BlockHash for $test_struct_name {
+            const BLOCK_SIZE: usize = <$hash_type as BlockSizeUser>::BlockSize::USIZE;
         }
     };
 }
@@ -85,6 +138,7 @@ impl_hash_metamorphic! {Tiger, TigerMetamorphic, "Tiger"}
 impl_hash_metamorphic! {Sm3, Sm3Metamorphic, "Sm3"}
 impl_hash_metamorphic! {Skein256<U32>, Skein256Metamorphic, "Skein256"}
 impl_hash_metamorphic! {Skein512<U32>, Skein512Metamorphic, "Skein512"}
+impl_hash_metamorphic! {Skein512<U64>, Skein512_512Metamorphic, "Skein512_512"}
 impl_hash_metamorphic! {Skein1024<U32>, Skein1024Metamorphic, "Skein1024"}
 impl_hash_metamorphic! {Shabal192, Shabal192Metamorphic, "Shabal192"}
 impl_hash_metamorphic! {Shabal224, Shabal224Metamorphic, "Shabal224"}
@@ -111,6 +165,7 @@ impl_hash_metamorphic! {Fsb512, Fsb512Metamorphic, "Fsb512"}
 impl_hash_metamorphic! {AsconHash, AsconMetamorphic, "Ascon"}
 impl_hash_metamorphic! {BeltHash, BeltMetamorphic, "BelT"}
 impl_hash_metamorphic! {Blake2b512, Blake2b512Metamorphic, "Blake2b512"}
+impl_hash_metamorphic! {Blake2b<U32>, Blake2b256Metamorphic, "Blake2b256"}
 impl_hash_metamorphic! {Blake2s256, Blake2s256Metamorphic, "Blake2s256"}
 impl_hash_metamorphic! {Sha224, Sha2_224Metamorphic, "Sha2_224"}
 impl_hash_metamorphic! {Sha256, Sha2_256Metamorphic, "Sha2_256"}
@@ -133,7 +188,7 @@ impl HashMetamorphic for Blake3Metamorphic {
 
     fn gen_input(size: usize) -> Self::Input {
         let mut data = vec![0u8; size];
-        rand::thread_rng().fill_bytes(&mut data);
+        campaign_rng().fill_bytes(&mut data);
         data
     }
 
@@ -179,10 +234,151 @@ impl HashMetamorphic for Blake3Metamorphic {
         state.update(first_part);
         (state, second_part.to_vec())
     }
+
+    fn hash_api_paths(message: &[u8]) -> Vec<Vec<u8>> {
+        let mut paths = vec![blake3::hash(message).as_bytes().to_vec()];
+        paths.push(
+            Hasher::new()
+                .update_rayon(message)
+                .finalize()
+                .as_bytes()
+                .to_vec(),
+        );
+
+        let mut prefix = [0u8; 32];
+        Hasher::new()
+            .update(message)
+            .finalize_xof()
+            .fill(&mut prefix);
+        paths.push(prefix.to_vec());
+
+        // finalize does not consume the hasher, finalizing twice must give the same digest
+        let mut hasher = Hasher::new();
+        hasher.update(API_PATHS_GARBAGE);
+        hasher.reset();
+        hasher.update(message);
+        paths.push(hasher.finalize().as_bytes().to_vec());
+        paths.push(hasher.finalize().as_bytes().to_vec());
+
+        let mut hasher = Hasher::new();
+        hasher.write_all(message).unwrap();
+        paths.push(hasher.finalize().as_bytes().to_vec());
+        paths
+    }
+}
+
+impl BlockHash for Blake3Metamorphic {
+    const BLOCK_SIZE: usize = blake3::BLOCK_LEN;
+}
+
+const BLAKE3_CHUNK_SIZE: usize = 1024;
+
+/// Input lengths around the Blake3 chunk (1 KiB) and subtree (16 KiB and above) boundaries
+fn blake3_boundary_sizes() -> Vec<usize> {
+    let mut sizes = vec![0, 1];
+    for chunks in [1, 2, 3, 4, 8, 15, 16, 17, 31, 32, 33, 64, 128, 1024] {
+        let size = chunks * BLAKE3_CHUNK_SIZE;
+        sizes.extend([size - 1, size, size + 1]);
+    }
+    sizes
+}
+
+impl Blake3Metamorphic {
+    fn gen_boundary_input(_size: usize) -> (Vec<u8>, usize) {
+        let max_size = *blake3_boundary_sizes().iter().max().unwrap();
+        let mut data = vec![0u8; max_size];
+        campaign_rng().fill_bytes(&mut data);
+        (data, 0)
+    }
+
+    /// Runner positions start at 1, position n hashes the boundary size n - 1
+    fn set_boundary_size(
+        _state: &(),
+        input: &(Vec<u8>, usize),
+        element_to_mutate: usize,
+    ) -> ((), (Vec<u8>, usize)) {
+        let sizes = blake3_boundary_sizes();
+        ((), (input.0.clone(), sizes[element_to_mutate - 1]))
+    }
+
+    /// Serial digest, then digests through the parallel, reader and memory-mapped paths
+    fn call_test_parallel_paths(_state: (), input: &(Vec<u8>, usize)) -> Vec<Vec<u8>> {
+        let data = &input.0[..input.1];
+        let mut digests = vec![Hasher::new().update(data).finalize().as_bytes().to_vec()];
+        digests.push(
+            Hasher::new()
+                .update_rayon(data)
+                .finalize()
+                .as_bytes()
+                .to_vec(),
+        );
+
+        let path = std::env::temp_dir().join(format!(
+            "metamorphic-testing-rs-blake3-{}-{}",
+            std::process::id(),
+            rand::thread_rng().next_u64()
+        ));
+        std::fs::write(&path, data).unwrap();
+        digests.push(
+            Hasher::new()
+                .update_reader(std::fs::File::open(&path).unwrap())
+                .unwrap()
+                .finalize()
+                .as_bytes()
+                .to_vec(),
+        );
+        digests.push(
+            Hasher::new()
+                .update_mmap(&path)
+                .unwrap()
+                .finalize()
+                .as_bytes()
+                .to_vec(),
+        );
+        digests.push(
+            Hasher::new()
+                .update_mmap_rayon(&path)
+                .unwrap()
+                .finalize()
+                .as_bytes()
+                .to_vec(),
+        );
+        std::fs::remove_file(&path).unwrap();
+        digests
+    }
+
+    /// `update_rayon`, `update_reader`, `update_mmap` and `update_mmap_rayon` must match serial
+    /// hashing on inputs crossing chunk and subtree boundaries
+    pub fn parallel_paths_relation() -> impl MetamorphicRelation<(), (Vec<u8>, usize), Vec<Vec<u8>>>
+    {
+        Relation::new(
+            "Parallel and memory-mapped paths",
+            IndexMutator::new(Self::set_boundary_size),
+            |output: &Vec<Vec<u8>>, _reference_output: &Vec<Vec<u8>>| {
+                output.iter().all(|digest| *digest == output[0])
+            },
+        )
+    }
+
+    pub fn parallel_paths_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_boundary_input,
+            || (),
+            Self::call_test_parallel_paths,
+        )
+        .with_options(options.clone())
+        // One position per boundary size
+        .run_relation(
+            blake3_boundary_sizes().len() + 1,
+            blake3_boundary_sizes().len() + 1,
+            Self::LIBNAME,
+            &Self::parallel_paths_relation(),
+        );
+    }
 }
 
 macro_rules! impl_hash_metamorphic_ring {
-    ($hash_type:expr, $test_struct_name:ident, $libname:literal) => {
+    ($hash_type:expr, $test_struct_name:ident, $libname:literal, $block_size:literal) => {
         pub struct $test_struct_name {}
         impl HashMetamorphic for $test_struct_name {
             type Input = Vec<u8>;
@@ -193,7 +389,7 @@ macro_rules! impl_hash_metamorphic_ring {
 
             fn gen_input(size: usize) -> Self::Input {
                 let mut data = vec![0u8; size];
-                rand::thread_rng().fill_bytes(&mut data);
+                campaign_rng().fill_bytes(&mut data);
                 data
             }
 
@@ -239,11 +435,115 @@ macro_rules! impl_hash_metamorphic_ring {
                 state.update(first_part);
                 (state, second_part.to_vec())
             }
+
+            fn hash_api_paths(message: &[u8]) -> Vec<Vec<u8>> {
+                vec![ring::digest::digest(&$hash_type, message).as_ref().to_vec()]
+            }
+        }
+
+        impl BlockHash for $test_struct_name {
+            const BLOCK_SIZE: usize = $block_size;
         }
     };
 }
 
-impl_hash_metamorphic_ring! {ring::digest::SHA256, RingSHA256Metamorphic, "Ring SHA256"}
-impl_hash_metamorphic_ring! {ring::digest::SHA384, RingSHA384Metamorphic, "Ring SHA384"}
-impl_hash_metamorphic_ring! {ring::digest::SHA512, RingSHA512Metamorphic, "Ring SHA512"}
-impl_hash_metamorphic_ring! {ring::digest::SHA512_256, RingSHA512_256Metamorphic, "Ring SHA512_256"}
+impl_hash_metamorphic_ring! {ring::digest::SHA256, RingSHA256Metamorphic, "Ring SHA256", 64}
+impl_hash_metamorphic_ring! {ring::digest::SHA384, RingSHA384Metamorphic, "Ring SHA384", 128}
+impl_hash_metamorphic_ring! {ring::digest::SHA512, RingSHA512Metamorphic, "Ring SHA512", 128}
+impl_hash_metamorphic_ring! {ring::digest::SHA512_256, RingSHA512_256Metamorphic, "Ring SHA512_256", 128}
+
+/// Unkeyed Blake2b-512 with a personalization string of at most 16 bytes
+pub fn blake2b_personalized_test(
+    persona: &[u8],
+    max_size: usize,
+    options: &CampaignOptions,
+) -> usize {
+    let lib_name = format!("Blake2b512 persona {}", String::from_utf8_lossy(persona));
+    let persona = persona.to_vec();
+    configured_hash_test(
+        &lib_name,
+        move || Blake2bMac512::new_with_salt_and_personal(&[], &[], &persona).unwrap(),
+        Mac::update,
+        |hasher| hasher.finalize().into_bytes().to_vec(),
+        max_size,
+        options,
+    )
+}
+
+/// Blake2b with an output size of 1 to 64 bytes chosen at runtime
+pub fn blake2b_var_test(output_size: usize, max_size: usize, options: &CampaignOptions) -> usize {
+    configured_hash_test(
+        &format!("Blake2bVar-{}", output_size * 8),
+        move || Blake2bVar::new(output_size).unwrap(),
+        blake2::digest::Update::update,
+        |hasher| hasher.finalize_boxed().to_vec(),
+        max_size,
+        options,
+    )
+}
+
+/// Blake2b binds the output size in its parameter block, so outputs of different lengths are
+/// unrelated hashes: the shorter one must not be a prefix of the longer one
+pub fn blake2b_var_distinctness_relation(
+) -> impl MetamorphicRelation<(), Vec<u8>, (Vec<u8>, Vec<u8>)> {
+    Relation::new(
+        "Variable output distinctness",
+        BitFlipMutator::whole(),
+        |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| {
+            !output.1.starts_with(&output.0)
+        },
+    )
+}
+
+pub fn blake2b_var_distinctness_test(
+    short_size: usize,
+    long_size: usize,
+    max_size: usize,
+    options: &CampaignOptions,
+) -> usize {
+    let blake2b_var = |output_size: usize, input: &[u8]| {
+        let mut hasher = Blake2bVar::new(output_size).unwrap();
+        blake2::digest::Update::update(&mut hasher, input);
+        hasher.finalize_boxed().to_vec()
+    };
+    MetamorphicTarget::new(
+        |size| {
+            let mut data = vec![0u8; size];
+            campaign_rng().fill_bytes(&mut data);
+            data
+        },
+        || (),
+        move |_state, input: &Vec<u8>| {
+            (
+                blake2b_var(short_size, input),
+                blake2b_var(long_size, input),
+            )
+        },
+    )
+    .with_options(options.clone())
+    .run_relation(
+        1,
+        max_size,
+        &format!(
+            "Blake2bVar-{} vs Blake2bVar-{}",
+            short_size * 8,
+            long_size * 8
+        ),
+        &blake2b_var_distinctness_relation(),
+    )
+}
+
+/// Skein-512 with an output size of `N` bytes
+pub fn skein512_output_size_test<N: ArrayLength<u8> + Send + Sync + 'static>(
+    max_size: usize,
+    options: &CampaignOptions,
+) -> usize {
+    configured_hash_test(
+        &format!("Skein512-{}", N::USIZE * 8),
+        Skein512::<N>::new,
+        |hasher, data| Digest::update(hasher, data),
+        |hasher| hasher.finalize().to_vec(),
+        max_size,
+        options,
+    )
+}
@@ -0,0 +1,75 @@
+use crate::{campaign_rng, KdfMetamorphic};
+use blake3::Hasher;
+use rand;
+use rand::Rng;
+
+/// Requested output size for ring's HKDF
+struct OutputSize(usize);
+
+impl ring::hkdf::KeyType for OutputSize {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+macro_rules! impl_kdf_metamorphic_ring {
+    ($hkdf_algorithm:expr, $test_struct_name:ident, $libname:literal, $hash_size:literal) => {
+        pub struct $test_struct_name {}
+        impl KdfMetamorphic for $test_struct_name {
+            const LIBNAME: &str = $libname;
+
+            const SALTSIZE: usize = $hash_size;
+
+            const INFOSIZE: usize = 32;
+
+            const MAX_OUTPUT_SIZE: usize = 255 * $hash_size;
+
+            fn derive(salt: &[u8], ikm: &[u8], info: &[u8], output_size: usize) -> Vec<u8> {
+                let prk = ring::hkdf::Salt::new($hkdf_algorithm, salt).extract(ikm);
+                let mut output = vec![0u8; output_size];
+                prk.expand(&[info], OutputSize(output_size))
+                    .unwrap()
+                    .fill(&mut output)
+                    .unwrap();
+                output
+            }
+        }
+    };
+}
+
+impl_kdf_metamorphic_ring! {ring::hkdf::HKDF_SHA256, RingHkdfSha256Metamorphic, "Ring HKDF SHA256", 32}
+impl_kdf_metamorphic_ring! {ring::hkdf::HKDF_SHA384, RingHkdfSha384Metamorphic, "Ring HKDF SHA384", 48}
+impl_kdf_metamorphic_ring! {ring::hkdf::HKDF_SHA512, RingHkdfSha512Metamorphic, "Ring HKDF SHA512", 64}
+
+pub struct Blake3DeriveKeyMetamorphic {}
+impl KdfMetamorphic for Blake3DeriveKeyMetamorphic {
+    const LIBNAME: &str = "Blake3 derive_key";
+
+    const SALTSIZE: usize = 0;
+
+    const INFOSIZE: usize = 32;
+
+    const MAX_OUTPUT_SIZE: usize = usize::MAX;
+
+    /// Only the 7 low bits of each byte are flipped, so that the context stays ASCII
+    const INFO_MUTATIONS: usize = Self::INFOSIZE * 7;
+
+    /// Context strings are `&str`, generate them as ASCII
+    fn gen_info() -> Vec<u8> {
+        (0..Self::INFOSIZE)
+            .map(|_| campaign_rng().sample(rand::distributions::Alphanumeric))
+            .collect()
+    }
+
+    fn mutate_info(info: &mut [u8], element_to_mutate: usize) {
+        info[element_to_mutate / 7] ^= 1 << (element_to_mutate % 7);
+    }
+
+    fn derive(_salt: &[u8], ikm: &[u8], info: &[u8], output_size: usize) -> Vec<u8> {
+        let mut hasher = Hasher::new_derive_key(std::str::from_utf8(info).unwrap());
+        hasher.update(ikm);
+        let mut output = vec![0u8; output_size];
+        hasher.finalize_xof().fill(&mut output);
+        output
+    }
+}
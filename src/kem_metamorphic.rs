@@ -1,4 +1,57 @@
-use crate::KEMMetamorphic;
+use crate::{campaign_rng, CampaignOptions, KEMMetamorphic};
+use ml_kem::kem::{Decapsulate, DecapsulationKey, Encapsulate, EncapsulationKey};
+use ml_kem::{
+    Ciphertext, EncapsulateDeterministic, EncodedSizeUser, KemCore, MlKem1024, MlKem1024Params,
+    MlKem512, MlKem512Params, MlKem768, MlKem768Params,
+};
+use rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Shake256};
+
+/// Deterministic RNG replaying a fixed seed, used to derandomize APIs that only accept an RNG
+struct SeededRng {
+    seed: Vec<u8>,
+    position: usize,
+}
+
+impl SeededRng {
+    fn new(seed: &[u8]) -> Self {
+        Self {
+            seed: seed.to_vec(),
+            position: 0,
+        }
+    }
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap()
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        let end = self.position + dest.len();
+        if end > self.seed.len() {
+            return Err(rand::Error::new("seed exhausted"));
+        }
+        dest.copy_from_slice(&self.seed[self.position..end]);
+        self.position = end;
+        Ok(())
+    }
+}
+
+impl CryptoRng for SeededRng {}
 
 pub struct PQCKyberMetamorphic {}
 
@@ -19,12 +72,16 @@ impl KEMMetamorphic for PQCKyberMetamorphic {
 
     const SSSIZE: usize = 32;
 
+    const SUPPORTS_PUBLIC_FROM_SECRET: bool = true;
+
+    const SPECIFICATION: &str = "Kyber round 3";
+
     type State = ();
 
     const LIBNAME: &str = "Kyber Argyle";
 
     fn gen_keys() -> (Self::SecretKey, Self::PublicKey) {
-        let mut rng = rand::thread_rng();
+        let mut rng = campaign_rng();
         let keys = pqc_kyber::keypair(&mut rng).unwrap();
         (keys.secret, keys.public)
     }
@@ -34,15 +91,40 @@ impl KEMMetamorphic for PQCKyberMetamorphic {
     }
 
     fn encaps(pk: &Self::PublicKey) -> (Self::SharedSecret, Self::CipherText) {
-        let mut rng = rand::thread_rng();
+        let mut rng = campaign_rng();
         let res = pqc_kyber::encapsulate(pk, &mut rng).unwrap();
         (res.1, res.0)
     }
 
-    fn gen_state() -> Self::State {
-        ()
+    fn public_from_secret(sk: &Self::SecretKey) -> Option<Self::PublicKey> {
+        Some(pqc_kyber::public(sk))
     }
 
+    fn try_encaps(pk: &Self::PublicKey) -> Option<(Self::SharedSecret, Self::CipherText)> {
+        let mut rng = campaign_rng();
+        let res = pqc_kyber::encapsulate(pk, &mut rng).ok()?;
+        Some((res.1, res.0))
+    }
+
+    fn gen_keys_from_seed(
+        d: &[u8; 32],
+        z: &[u8; 32],
+    ) -> Option<(Self::SecretKey, Self::PublicKey)> {
+        let keys = pqc_kyber::derive(&[d.as_slice(), z.as_slice()].concat()).unwrap();
+        Some((keys.secret, keys.public))
+    }
+
+    fn encaps_from_seed(
+        pk: &Self::PublicKey,
+        m: &[u8; 32],
+    ) -> Option<(Self::SharedSecret, Self::CipherText)> {
+        let mut rng = SeededRng::new(m);
+        let res = pqc_kyber::encapsulate(pk, &mut rng).unwrap();
+        Some((res.1, res.0))
+    }
+
+    fn gen_state() -> Self::State {}
+
     fn get_skey_from_input_as_u8(
         input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
     ) -> Vec<u8> {
@@ -63,11 +145,17 @@ impl KEMMetamorphic for PQCKyberMetamorphic {
         Self::State,
         (Self::SecretKey, Self::PublicKey, Self::CipherText),
     ) {
-        let mut out = input.clone();
+        let mut out = *input;
         out.0 = key.try_into().unwrap();
         ((), out)
     }
 
+    fn skey_bytes_mut(
+        input: &mut (Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Option<&mut [u8]> {
+        Some(&mut input.0)
+    }
+
     fn set_pkey_from_input_as_u8(
         _state: &Self::State,
         input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
@@ -76,12 +164,245 @@ impl KEMMetamorphic for PQCKyberMetamorphic {
         Self::State,
         (Self::SecretKey, Self::PublicKey, Self::CipherText),
     ) {
-        let mut out = input.clone();
+        let mut out = *input;
         out.1 = key.try_into().unwrap();
         ((), out)
     }
 
+    fn get_ciphertext_from_input_as_u8(
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Option<Vec<u8>> {
+        Some(input.2.to_vec())
+    }
+
     fn output_as_u8(output: Self::SharedSecret) -> Vec<u8> {
         output.to_vec()
     }
 }
+
+macro_rules! impl_kem_metamorphic_ml_kem {
+    ($kem_type:ty, $params_type:ty, $test_struct_name:ident, $libname:literal, $pksize:literal, $sksize:literal, $ctsize:literal) => {
+        pub struct $test_struct_name {}
+
+        impl KEMMetamorphic for $test_struct_name {
+            type SecretKey = [u8; $sksize];
+
+            type PublicKey = [u8; $pksize];
+
+            type CipherText = [u8; $ctsize];
+
+            type SharedSecret = [u8; 32];
+
+            const PKSIZE: usize = $pksize;
+
+            const SKSIZE: usize = $sksize;
+
+            const CTSIZE: usize = $ctsize;
+
+            const SSSIZE: usize = 32;
+
+            const SUPPORTS_PUBLIC_FROM_SECRET: bool = true;
+
+            type State = ();
+
+            const LIBNAME: &str = $libname;
+
+            fn gen_keys() -> (Self::SecretKey, Self::PublicKey) {
+                let mut rng = campaign_rng();
+                let (dk, ek) = <$kem_type>::generate(&mut rng);
+                (
+                    dk.as_bytes().as_slice().try_into().unwrap(),
+                    ek.as_bytes().as_slice().try_into().unwrap(),
+                )
+            }
+
+            fn decaps(sk: &Self::SecretKey, ct: &Self::CipherText) -> Self::SharedSecret {
+                let dk =
+                    DecapsulationKey::<$params_type>::from_bytes(sk.as_slice().try_into().unwrap());
+                let ct: Ciphertext<$kem_type> = ct.as_slice().try_into().unwrap();
+                dk.decapsulate(&ct).unwrap().as_slice().try_into().unwrap()
+            }
+
+            fn encaps(pk: &Self::PublicKey) -> (Self::SharedSecret, Self::CipherText) {
+                let mut rng = campaign_rng();
+                let ek =
+                    EncapsulationKey::<$params_type>::from_bytes(pk.as_slice().try_into().unwrap());
+                let (ct, ss) = ek.encapsulate(&mut rng).unwrap();
+                (
+                    ss.as_slice().try_into().unwrap(),
+                    ct.as_slice().try_into().unwrap(),
+                )
+            }
+
+            fn public_from_secret(sk: &Self::SecretKey) -> Option<Self::PublicKey> {
+                let dk =
+                    DecapsulationKey::<$params_type>::from_bytes(sk.as_slice().try_into().unwrap());
+                Some(
+                    dk.encapsulation_key()
+                        .as_bytes()
+                        .as_slice()
+                        .try_into()
+                        .unwrap(),
+                )
+            }
+
+            fn try_encaps(pk: &Self::PublicKey) -> Option<(Self::SharedSecret, Self::CipherText)> {
+                let mut rng = campaign_rng();
+                let ek =
+                    EncapsulationKey::<$params_type>::from_bytes(pk.as_slice().try_into().unwrap());
+                let (ct, ss) = ek.encapsulate(&mut rng).ok()?;
+                Some((
+                    ss.as_slice().try_into().unwrap(),
+                    ct.as_slice().try_into().unwrap(),
+                ))
+            }
+
+            fn gen_keys_from_seed(
+                d: &[u8; 32],
+                z: &[u8; 32],
+            ) -> Option<(Self::SecretKey, Self::PublicKey)> {
+                let (dk, ek) = <$kem_type>::generate_deterministic(&(*d).into(), &(*z).into());
+                Some((
+                    dk.as_bytes().as_slice().try_into().unwrap(),
+                    ek.as_bytes().as_slice().try_into().unwrap(),
+                ))
+            }
+
+            fn encaps_from_seed(
+                pk: &Self::PublicKey,
+                m: &[u8; 32],
+            ) -> Option<(Self::SharedSecret, Self::CipherText)> {
+                let ek =
+                    EncapsulationKey::<$params_type>::from_bytes(pk.as_slice().try_into().unwrap());
+                let (ct, ss) = ek.encapsulate_deterministic(&(*m).into()).unwrap();
+                Some((
+                    ss.as_slice().try_into().unwrap(),
+                    ct.as_slice().try_into().unwrap(),
+                ))
+            }
+
+            fn gen_state() -> Self::State {}
+
+            fn get_skey_from_input_as_u8(
+                input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) -> Vec<u8> {
+                input.0.to_vec()
+            }
+
+            fn get_pkey_from_input_as_u8(
+                input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) -> Vec<u8> {
+                input.1.to_vec()
+            }
+
+            fn set_skey_from_input_as_u8(
+                _state: &Self::State,
+                input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+                key: Vec<u8>,
+            ) -> (
+                Self::State,
+                (Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) {
+                let mut out = *input;
+                out.0 = key.try_into().unwrap();
+                ((), out)
+            }
+
+            fn skey_bytes_mut(
+                input: &mut (Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) -> Option<&mut [u8]> {
+                Some(&mut input.0)
+            }
+
+            fn set_pkey_from_input_as_u8(
+                _state: &Self::State,
+                input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+                key: Vec<u8>,
+            ) -> (
+                Self::State,
+                (Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) {
+                let mut out = *input;
+                out.1 = key.try_into().unwrap();
+                ((), out)
+            }
+
+            fn get_ciphertext_from_input_as_u8(
+                input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+            ) -> Option<Vec<u8>> {
+                Some(input.2.to_vec())
+            }
+
+            fn output_as_u8(output: Self::SharedSecret) -> Vec<u8> {
+                output.to_vec()
+            }
+        }
+    };
+}
+
+impl_kem_metamorphic_ml_kem! {MlKem512, MlKem512Params, MlKem512Metamorphic, "ML-KEM-512 RustCrypto", 800, 1632, 768}
+impl_kem_metamorphic_ml_kem! {MlKem768, MlKem768Params, MlKem768Metamorphic, "ML-KEM-768 RustCrypto", 1184, 2400, 1088}
+impl_kem_metamorphic_ml_kem! {MlKem1024, MlKem1024Params, MlKem1024Metamorphic, "ML-KEM-1024 RustCrypto", 1568, 3168, 1568}
+
+/// SHA3-256, the `H` function of both round 3 Kyber and FIPS 203
+fn hash_h(data: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(data).into()
+}
+
+/// SHAKE256 to 32 bytes, the round 3 Kyber `KDF`
+fn kyber_kdf(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Shake256::default();
+    Update::update(&mut hasher, data);
+    let mut out = [0u8; 32];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// Compare pqc_kyber (round 3 Kyber768) and ML-KEM-768 on the parts both specifications share,
+/// their key generation and shared secret derivation differ. With an ML-KEM key pair, round 3
+/// encapsulation of `m` encrypts `H(m)` exactly as ML-KEM encapsulation of `H(m)` does, so the
+/// ciphertexts are identical and the round 3 secret is `KDF(K || H(c))` with `K` the ML-KEM
+/// secret. The secret keys share the `dk_pke || ek || H(ek) || z` layout, so each implementation
+/// must also decapsulate the other's ciphertext. The seeds are derived from the campaign seed,
+/// returns the number of failing seeds.
+pub fn kyber_ml_kem_shared_parts_test(iterations: usize, options: &CampaignOptions) -> usize {
+    let lib_name = options.scoped(&format!(
+        "{} vs {}",
+        PQCKyberMetamorphic::LIBNAME,
+        MlKem768Metamorphic::LIBNAME
+    ));
+    let mut number_of_errors = 0;
+    for iteration in 0..iterations {
+        if options.exhausted(number_of_errors) {
+            break;
+        }
+        let mut rng =
+            StdRng::seed_from_u64(options.size_seed(&lib_name, "Shared parts", iteration));
+        let (mut d, mut z, mut m) = ([0u8; 32], [0u8; 32], [0u8; 32]);
+        rng.fill_bytes(&mut d);
+        rng.fill_bytes(&mut z);
+        rng.fill_bytes(&mut m);
+
+        let (sk, pk) = MlKem768Metamorphic::gen_keys_from_seed(&d, &z).unwrap();
+        let (kyber_ss, kyber_ct) = PQCKyberMetamorphic::encaps_from_seed(&pk, &m).unwrap();
+        let (ml_kem_ss, ml_kem_ct) =
+            MlKem768Metamorphic::encaps_from_seed(&pk, &hash_h(&m)).unwrap();
+
+        let same_ciphertext = kyber_ct == ml_kem_ct;
+        let same_secret = kyber_ss == kyber_kdf(&[ml_kem_ss, hash_h(&ml_kem_ct)].concat());
+        let kyber_decaps = PQCKyberMetamorphic::decaps(&sk, &ml_kem_ct) == kyber_ss;
+        let ml_kem_decaps = MlKem768Metamorphic::decaps(&sk, &kyber_ct) == ml_kem_ss;
+        if !(same_ciphertext && same_secret && kyber_decaps && ml_kem_decaps) {
+            number_of_errors += 1;
+            println!(
+                "[{}] ! ERROR ! Running Shared parts on seed d {:?}, z {:?}, m {:?}: same ciphertext {}, same secret {}, round 3 decaps {}, ML-KEM decaps {}",
+                lib_name, d, z, m, same_ciphertext, same_secret, kyber_decaps, ml_kem_decaps
+            );
+        }
+    }
+    println!(
+        "[{}] !SUMMARY ! Shared parts with {} seeds : found {} errors",
+        lib_name, iterations, number_of_errors
+    );
+    number_of_errors
+}
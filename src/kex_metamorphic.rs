@@ -0,0 +1,177 @@
+use crate::{campaign_rng, KeyExchangeInput, KeyExchangeMetamorphic};
+use pqc_kyber::{
+    Ake, AkeSendInit, AkeSendResponse, Keypair, SharedSecret, Uake, UakeSendInit, UakeSendResponse,
+};
+
+pub struct PQCKyberUakeMetamorphic {}
+
+impl KeyExchangeMetamorphic for PQCKyberUakeMetamorphic {
+    /// Server keys, the client is not authenticated
+    type StaticKeys = Keypair;
+
+    type Session = Uake;
+
+    type InitMessage = UakeSendInit;
+
+    type ResponseMessage = UakeSendResponse;
+
+    type SharedSecret = SharedSecret;
+
+    const LIBNAME: &str = "Kyber Argyle UAKE";
+
+    const INITSIZE: usize = 2272;
+
+    const RESPONSESIZE: usize = 1088;
+
+    fn gen_static_keys() -> Self::StaticKeys {
+        let mut rng = campaign_rng();
+        pqc_kyber::keypair(&mut rng).unwrap()
+    }
+
+    fn new_session() -> Self::Session {
+        Uake::new()
+    }
+
+    fn client_init(client: &mut Self::Session, keys: &Self::StaticKeys) -> Self::InitMessage {
+        let mut rng = campaign_rng();
+        client.client_init(&keys.public, &mut rng).unwrap()
+    }
+
+    fn server_receive(
+        server: &mut Self::Session,
+        keys: &Self::StaticKeys,
+        init: &Self::InitMessage,
+    ) -> Option<Self::ResponseMessage> {
+        let mut rng = campaign_rng();
+        server.server_receive(*init, &keys.secret, &mut rng).ok()
+    }
+
+    fn client_confirm(
+        client: &mut Self::Session,
+        _keys: &Self::StaticKeys,
+        response: &Self::ResponseMessage,
+    ) -> bool {
+        client.client_confirm(*response).is_ok()
+    }
+
+    fn shared_secret(session: &Self::Session) -> Self::SharedSecret {
+        session.shared_secret
+    }
+
+    fn get_init_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8> {
+        input.init.to_vec()
+    }
+
+    fn set_init_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        init: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>) {
+        let mut out = input.clone();
+        out.init = init.try_into().unwrap();
+        ((), out)
+    }
+
+    fn get_response_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8> {
+        input.response.to_vec()
+    }
+
+    fn set_response_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        response: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>) {
+        let mut out = input.clone();
+        out.response = response.try_into().unwrap();
+        ((), out)
+    }
+}
+
+pub struct PQCKyberAkeMetamorphic {}
+
+impl KeyExchangeMetamorphic for PQCKyberAkeMetamorphic {
+    /// Client keys then server keys
+    type StaticKeys = (Keypair, Keypair);
+
+    type Session = Ake;
+
+    type InitMessage = AkeSendInit;
+
+    type ResponseMessage = AkeSendResponse;
+
+    type SharedSecret = SharedSecret;
+
+    const LIBNAME: &str = "Kyber Argyle AKE";
+
+    const INITSIZE: usize = 2272;
+
+    const RESPONSESIZE: usize = 2176;
+
+    fn gen_static_keys() -> Self::StaticKeys {
+        let mut rng = campaign_rng();
+        (
+            pqc_kyber::keypair(&mut rng).unwrap(),
+            pqc_kyber::keypair(&mut rng).unwrap(),
+        )
+    }
+
+    fn new_session() -> Self::Session {
+        Ake::new()
+    }
+
+    fn client_init(client: &mut Self::Session, keys: &Self::StaticKeys) -> Self::InitMessage {
+        let mut rng = campaign_rng();
+        client.client_init(&keys.1.public, &mut rng).unwrap()
+    }
+
+    fn server_receive(
+        server: &mut Self::Session,
+        keys: &Self::StaticKeys,
+        init: &Self::InitMessage,
+    ) -> Option<Self::ResponseMessage> {
+        let mut rng = campaign_rng();
+        server
+            .server_receive(*init, &keys.0.public, &keys.1.secret, &mut rng)
+            .ok()
+    }
+
+    fn client_confirm(
+        client: &mut Self::Session,
+        keys: &Self::StaticKeys,
+        response: &Self::ResponseMessage,
+    ) -> bool {
+        client.client_confirm(*response, &keys.0.secret).is_ok()
+    }
+
+    fn shared_secret(session: &Self::Session) -> Self::SharedSecret {
+        session.shared_secret
+    }
+
+    fn get_init_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8> {
+        input.init.to_vec()
+    }
+
+    fn set_init_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        init: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>) {
+        let mut out = input.clone();
+        out.init = init.try_into().unwrap();
+        ((), out)
+    }
+
+    fn get_response_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8> {
+        input.response.to_vec()
+    }
+
+    fn set_response_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        response: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>) {
+        let mut out = input.clone();
+        out.response = response.try_into().unwrap();
+        ((), out)
+    }
+}
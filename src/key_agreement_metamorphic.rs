@@ -0,0 +1,155 @@
+use crate::{GeneratedKeyAgreementMetamorphic, KeyAgreementMetamorphic};
+use hex_literal::hex;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use ring::agreement::{EphemeralPrivateKey, UnparsedPublicKey};
+use ring::rand::SystemRandom;
+
+/// Curve25519 points of small order, including unreduced encodings of 0 and 1
+fn x25519_low_order_points() -> Vec<Vec<u8>> {
+    [
+        hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        hex!("e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800"),
+        hex!("5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157"),
+        hex!("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"),
+        hex!("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"),
+        hex!("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"),
+    ]
+    .iter()
+    .map(|point| point.to_vec())
+    .collect()
+}
+
+/// The top bit of the last byte is masked when decoding (RFC 7748)
+fn x25519_normalize_public_key(public_key: &[u8]) -> Vec<u8> {
+    let mut normalized = public_key.to_vec();
+    normalized[31] &= 0x7f;
+    normalized
+}
+
+const P256_GX: [u8; 32] = hex!("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+const P256_GY: [u8; 32] = hex!("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+const P256_P: [u8; 32] = hex!("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+
+/// Uncompressed SEC1 encoding of the point (x, y)
+fn p256_uncompressed(x: &[u8; 32], y: &[u8; 32]) -> Vec<u8> {
+    [&[0x04][..], x, y].concat()
+}
+
+/// Point at infinity, points off the curve and unreduced coordinates, SEC1 encoded
+fn p256_invalid_points() -> Vec<Vec<u8>> {
+    let mut off_curve = p256_uncompressed(&P256_GX, &P256_GY);
+    off_curve[64] ^= 1;
+    vec![
+        vec![0x00],
+        vec![0x04; 65],
+        p256_uncompressed(&[0; 32], &[0; 32]),
+        off_curve,
+        p256_uncompressed(&P256_P, &P256_GY),
+        p256_uncompressed(&P256_GX, &P256_P),
+    ]
+}
+
+/// ring only generates private keys from its own random source, they are checked against a
+/// reference implementation that can import private scalars
+macro_rules! impl_generated_key_agreement_metamorphic_ring {
+    ($algorithm:expr, $struct_name:ident, $libname:expr, $reference:ty) => {
+        pub struct $struct_name {}
+        impl GeneratedKeyAgreementMetamorphic for $struct_name {
+            type PrivateKey = EphemeralPrivateKey;
+            type Reference = $reference;
+
+            const LIBNAME: &str = $libname;
+
+            fn generate() -> (Self::PrivateKey, Vec<u8>) {
+                // Drawn from the OS, ring keys are not replayed from the campaign seed
+                let private_key =
+                    EphemeralPrivateKey::generate(&$algorithm, &SystemRandom::new()).unwrap();
+                let public_key = private_key.compute_public_key().unwrap().as_ref().to_vec();
+                (private_key, public_key)
+            }
+
+            fn agree(private_key: Self::PrivateKey, peer_public_key: &[u8]) -> Option<Vec<u8>> {
+                ring::agreement::agree_ephemeral(
+                    private_key,
+                    &UnparsedPublicKey::new(&$algorithm, peer_public_key),
+                    |shared_secret| shared_secret.to_vec(),
+                )
+                .ok()
+            }
+        }
+    };
+}
+
+impl_generated_key_agreement_metamorphic_ring!(
+    ring::agreement::X25519,
+    RingX25519Metamorphic,
+    "Ring X25519",
+    X25519DalekMetamorphic
+);
+impl_generated_key_agreement_metamorphic_ring!(
+    ring::agreement::ECDH_P256,
+    RingEcdhP256Metamorphic,
+    "Ring ECDH P-256",
+    EcdhP256Metamorphic
+);
+
+pub struct X25519DalekMetamorphic {}
+impl KeyAgreementMetamorphic for X25519DalekMetamorphic {
+    const LIBNAME: &str = "X25519 Dalek";
+    const PRIVATESIZE: usize = 32;
+    const PUBLICSIZE: usize = 32;
+
+    fn public_key(private_key: &[u8]) -> Vec<u8> {
+        let secret = x25519_dalek::StaticSecret::from(<[u8; 32]>::try_from(private_key).unwrap());
+        x25519_dalek::PublicKey::from(&secret).as_bytes().to_vec()
+    }
+
+    /// Non contributory exchanges (all zero shared secret) are rejected, as ring does
+    fn agree(private_key: &[u8], peer_public_key: &[u8]) -> Option<Vec<u8>> {
+        let secret = x25519_dalek::StaticSecret::from(<[u8; 32]>::try_from(private_key).ok()?);
+        let peer_public_key =
+            x25519_dalek::PublicKey::from(<[u8; 32]>::try_from(peer_public_key).ok()?);
+        let shared_secret = secret.diffie_hellman(&peer_public_key);
+        if !shared_secret.was_contributory() {
+            return None;
+        }
+        Some(shared_secret.as_bytes().to_vec())
+    }
+
+    fn invalid_public_keys() -> Vec<Vec<u8>> {
+        x25519_low_order_points()
+    }
+
+    fn normalize_public_key(public_key: &[u8]) -> Vec<u8> {
+        x25519_normalize_public_key(public_key)
+    }
+}
+
+pub struct EcdhP256Metamorphic {}
+impl KeyAgreementMetamorphic for EcdhP256Metamorphic {
+    const LIBNAME: &str = "ECDH P-256 RustCrypto";
+    const PRIVATESIZE: usize = 32;
+    const PUBLICSIZE: usize = 65;
+
+    fn public_key(private_key: &[u8]) -> Vec<u8> {
+        p256::SecretKey::from_slice(private_key)
+            .unwrap()
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn agree(private_key: &[u8], peer_public_key: &[u8]) -> Option<Vec<u8>> {
+        let secret = p256::SecretKey::from_slice(private_key).ok()?;
+        let peer_public_key = p256::PublicKey::from_sec1_bytes(peer_public_key).ok()?;
+        let shared_secret =
+            p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), peer_public_key.as_affine());
+        Some(shared_secret.raw_secret_bytes().to_vec())
+    }
+
+    fn invalid_public_keys() -> Vec<Vec<u8>> {
+        p256_invalid_points()
+    }
+}
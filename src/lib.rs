@@ -1,293 +1,4053 @@
+pub mod aead_metamorphic;
+pub mod cipher_metamorphic;
 pub mod hash_metamorphic;
+pub mod kdf_metamorphic;
 pub mod kem_metamorphic;
+pub mod kex_metamorphic;
+pub mod key_agreement_metamorphic;
+pub mod mac_metamorphic;
+pub mod mutation_analysis;
+pub mod password_hash_metamorphic;
+pub mod signature_metamorphic;
+pub mod xof_metamorphic;
 
-use std::{ops::Deref, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs::{File, OpenOptions},
+    hash::{Hash, Hasher},
+    io::{Read, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
 
+use rand::{rngs::StdRng, CryptoRng, Rng, RngCore, SeedableRng};
 use rayon::prelude::*;
 
-pub struct MetamorphicTestRunner<State, Input, Output> {
+/// Number of mutations of a source input run by one parallel task
+const MUTATION_CHUNK_SIZE: usize = 64;
+
+/// Limits of a test campaign, applied to each relation run
+#[derive(Clone, Debug)]
+pub struct CampaignOptions {
+    /// Seed of the randomness of the runner, e.g. the generated inputs and the sampled positions
+    seed: u64,
+    /// Prefix of the library names, to tell apart targets sharing one (e.g. mutants of hashes)
+    scope: Option<String>,
+    /// No new mutation is started after this instant
+    deadline: Option<Instant>,
+    /// A relation run stops once it found this many errors
+    max_findings: Option<usize>,
+    /// Number of mutation positions drawn at random for each input size, all positions if None
+    sampled_mutations: Option<usize>,
+    /// Progress file, completed chunks of mutations are recorded in it and skipped when resuming
+    checkpoint: Option<Arc<Checkpoint>>,
+}
+
+impl Default for CampaignOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CampaignOptions {
+    /// Options without limits and with a random seed
+    pub fn new() -> Self {
+        Self {
+            seed: rand::random(),
+            scope: None,
+            deadline: None,
+            max_findings: None,
+            sampled_mutations: None,
+            checkpoint: None,
+        }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Seed of the runs, the one recorded in the checkpoint when resuming from one
+    pub fn seed(&self) -> u64 {
+        self.checkpoint
+            .as_ref()
+            .map_or(self.seed, |checkpoint| checkpoint.seed)
+    }
+
+    /// Library names of the runs become `{scope} + {library}`
+    pub fn with_scope(self, scope: &str) -> Self {
+        Self {
+            scope: Some(scope.to_string()),
+            ..self
+        }
+    }
+
+    fn scoped(&self, lib_name: &str) -> String {
+        match &self.scope {
+            Some(scope) => format!("{} + {}", scope, lib_name),
+            None => lib_name.to_string(),
+        }
+    }
+
+    /// Seed of the runner randomness for one input size of a relation run
+    fn size_seed(&self, lib_name: &str, test_name: &str, size: usize) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.seed(), lib_name, test_name, size).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The budget starts now and is shared by every run made with these options
+    pub fn with_time_budget(self, budget: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + budget),
+            ..self
+        }
+    }
+
+    pub fn with_max_findings(self, max_findings: usize) -> Self {
+        Self {
+            max_findings: Some(max_findings),
+            ..self
+        }
+    }
+
+    pub fn with_sampled_mutations(self, sampled_mutations: usize) -> Self {
+        Self {
+            sampled_mutations: Some(sampled_mutations),
+            ..self
+        }
+    }
+
+    /// Record progress in `path`, resuming from the chunks and with the seed it already holds.
+    /// A file marked finished by `finish` starts a new campaign
+    pub fn with_checkpoint(self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            checkpoint: Some(Arc::new(Checkpoint::open(path.as_ref(), self.seed)?)),
+            ..self
+        })
+    }
+
+    /// Mark the checkpoint as finished if every run recorded all its chunks, so that the next
+    /// campaign does not replay this one
+    pub fn finish(&self) -> std::io::Result<()> {
+        match &self.checkpoint {
+            Some(checkpoint) => checkpoint.finish(),
+            None => Ok(()),
+        }
+    }
+
+    fn deadline_passed(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    fn exhausted(&self, findings: usize) -> bool {
+        self.deadline_passed() || self.max_findings.is_some_and(|max| findings >= max)
+    }
+
+    /// Number of mutation positions run on an input of `size`
+    fn position_count(&self, size: usize) -> usize {
+        let count = size.saturating_sub(1);
+        self.sampled_mutations
+            .map_or(count, |sampled| sampled.min(count))
+    }
+
+    /// Mutation positions to run on an input of `size`, in increasing order
+    fn positions(&self, size: usize, rng: &mut StdRng) -> Vec<usize> {
+        let count = size.saturating_sub(1);
+        match self.sampled_mutations {
+            Some(sampled) if sampled < count => {
+                let mut positions: Vec<usize> = rand::seq::index::sample(rng, count, sampled)
+                    .into_iter()
+                    .map(|position| position + 1)
+                    .collect();
+                positions.sort_unstable();
+                positions
+            }
+            _ => (1..size).collect(),
+        }
+    }
+}
+
+/// Identify one chunk of mutations of a run: library, relation, size range, size, number of
+/// sampled mutations and index of the chunk among the mutations of the size
+type CheckpointKey = (String, String, usize, usize, usize, Option<usize>, usize);
+
+/// Result of a completed chunk: number of mutations run, and the position and mutator seed of
+/// each error, enough to replay it with the campaign seed
+#[derive(Clone, Debug)]
+struct CheckpointRecord {
+    ran: usize,
+    findings: Vec<(usize, Option<u64>)>,
+}
+
+/// Last line of the progress file of a completed campaign
+const CHECKPOINT_FINISHED: &str = "finished";
+
+/// Append-only progress file: a `seed \t seed` line, then one line per completed chunk
+/// `lib \t relation \t min \t max \t size \t sampled \t chunk \t ran \t position[:seed]...` and a
+/// `finished` line once the campaign completed
+#[derive(Debug)]
+struct Checkpoint {
+    seed: u64,
+    completed: HashMap<CheckpointKey, CheckpointRecord>,
+    file: Mutex<File>,
+    /// Set when a chunk was not recorded, the campaign is then not finished
+    incomplete: AtomicBool,
+}
+
+impl Checkpoint {
+    /// `seed` is recorded in a new file, an existing one keeps its own
+    fn open(path: &Path, seed: u64) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        // A line without its newline was cut by the interruption: its chunk is run again, and the
+        // line is dropped so that the next records start on their own line
+        if !content.ends_with('\n') {
+            content.truncate(content.rfind('\n').map_or(0, |end| end + 1));
+            file.set_len(content.len() as u64)?;
+        }
+        let lines: Vec<&str> = content.lines().collect();
+        let recorded_seed = lines
+            .first()
+            .and_then(|line| line.strip_prefix("seed\t"))
+            .and_then(|seed| seed.parse().ok());
+        let (seed, lines) = match recorded_seed {
+            Some(seed) if lines.last() != Some(&CHECKPOINT_FINISHED) => (seed, &lines[1..]),
+            // New campaign: empty file, previous campaign finished, or no seed to resume with
+            _ => {
+                file.set_len(0)?;
+                file.write_all(format!("seed\t{}\n", seed).as_bytes())?;
+                file.flush()?;
+                (seed, &[][..])
+            }
+        };
+        let mut completed = HashMap::new();
+        for line in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 8 {
+                continue;
+            }
+            let sampled = match fields[5] {
+                "all" => Some(None),
+                sampled => sampled.parse().ok().map(Some),
+            };
+            let findings: Option<Vec<(usize, Option<u64>)>> = fields[8..]
+                .iter()
+                .map(|finding| match finding.split_once(':') {
+                    Some((position, seed)) => {
+                        Some((position.parse().ok()?, Some(seed.parse().ok()?)))
+                    }
+                    None => Some((finding.parse().ok()?, None)),
+                })
+                .collect();
+            let (
+                Ok(min_size),
+                Ok(max_size),
+                Ok(size),
+                Some(sampled),
+                Ok(chunk),
+                Ok(ran),
+                Some(findings),
+            ) = (
+                fields[2].parse(),
+                fields[3].parse(),
+                fields[4].parse(),
+                sampled,
+                fields[6].parse(),
+                fields[7].parse(),
+                findings,
+            )
+            else {
+                continue;
+            };
+            completed.insert(
+                (
+                    fields[0].to_string(),
+                    fields[1].to_string(),
+                    min_size,
+                    max_size,
+                    size,
+                    sampled,
+                    chunk,
+                ),
+                CheckpointRecord { ran, findings },
+            );
+        }
+        Ok(Self {
+            seed,
+            completed,
+            file: Mutex::new(file),
+            incomplete: AtomicBool::new(false),
+        })
+    }
+
+    fn get(&self, key: &CheckpointKey) -> Option<&CheckpointRecord> {
+        self.completed.get(key)
+    }
+
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        // One write per line, so that an interruption can only cut the last line
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        file.write_all(format!("{}\n", line).as_bytes())?;
+        file.flush()
+    }
+
+    fn record(&self, key: &CheckpointKey, record: &CheckpointRecord) -> std::io::Result<()> {
+        let mut line = format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            key.0,
+            key.1,
+            key.2,
+            key.3,
+            key.4,
+            key.5
+                .map_or("all".to_string(), |sampled| sampled.to_string()),
+            key.6,
+            record.ran
+        );
+        for (position, seed) in &record.findings {
+            line.push_str(&format!("\t{}", position));
+            if let Some(seed) = seed {
+                line.push_str(&format!(":{}", seed));
+            }
+        }
+        self.write_line(&line)
+    }
+
+    fn mark_incomplete(&self) {
+        self.incomplete.store(true, Ordering::Relaxed);
+    }
+
+    /// Only a campaign that recorded all its chunks is finished, an incomplete one is resumed
+    fn finish(&self) -> std::io::Result<()> {
+        if self.incomplete.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        self.write_line(CHECKPOINT_FINISHED)
+    }
+}
+
+thread_local! {
+    /// Randomness of the generators, reseeded by the runner before generating the source input of
+    /// each size
+    static CAMPAIGN_RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+fn reseed_campaign_rng(seed: u64) {
+    CAMPAIGN_RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Randomness of the input, state and key generators: a run replays the inputs of a campaign from
+/// its seed. Keys generated by ring are the exception, its `SystemRandom` cannot be seeded
+#[derive(Clone, Copy, Debug)]
+pub struct CampaignRng;
+
+/// Handle on the campaign randomness of the current thread, seeded from the OS outside of a run
+pub fn campaign_rng() -> CampaignRng {
+    CampaignRng
+}
+
+impl RngCore for CampaignRng {
+    fn next_u32(&mut self) -> u32 {
+        CAMPAIGN_RNG.with(|rng| rng.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        CAMPAIGN_RNG.with(|rng| rng.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        CAMPAIGN_RNG.with(|rng| rng.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        CAMPAIGN_RNG.with(|rng| rng.borrow_mut().try_fill_bytes(dest))
+    }
+}
+
+impl CryptoRng for CampaignRng {}
+
+type CallFn<State, Input, Output> = Arc<dyn Fn(State, &Input) -> Output + Send + Sync>;
+type CheckFn<Output> = Arc<dyn Fn(&Output, &Output) -> bool + Send + Sync>;
+
+/// Function under test: generators of its inputs and states, and the call returning an output
+pub struct MetamorphicTarget<State, Input, Output> {
     /// Generate an input from a size
-    gen_input: fn(usize) -> Input,
+    gen_input: Arc<dyn Fn(usize) -> Input + Send + Sync>,
     /// Generate an initial state
-    gen_state: fn() -> State,
+    gen_state: Arc<dyn Fn() -> State + Send + Sync>,
     /// Call the function on input and state and return an output
-    call: fn(State, &Input) -> Output,
-    /// Check if the output match the condition
-    check: fn(&Output, &Output) -> bool,
+    call: CallFn<State, Input, Output>,
+    options: CampaignOptions,
 }
 
 impl<
-        State: Clone + Send,
-        Input: std::fmt::Debug + Send + Clone,
-        Output: std::fmt::Debug + Send + Clone,
-    > MetamorphicTestRunner<State, Input, Output>
+        State: Clone + Send + Sync,
+        Input: std::fmt::Debug + Send + Sync + Clone,
+        Output: std::fmt::Debug + Send + Sync + Clone,
+    > MetamorphicTarget<State, Input, Output>
 {
-    fn new(
-        gen_input: fn(usize) -> Input,
-        gen_state: fn() -> State,
-        call: fn(State, &Input) -> Output,
-        check: fn(&Output, &Output) -> bool,
+    /// The closures may capture the configuration of the target (key, parameters, output size)
+    pub fn new(
+        gen_input: impl Fn(usize) -> Input + Send + Sync + 'static,
+        gen_state: impl Fn() -> State + Send + Sync + 'static,
+        call: impl Fn(State, &Input) -> Output + Send + Sync + 'static,
     ) -> Self {
         Self {
-            gen_input,
-            gen_state,
-            call,
-            check,
+            gen_input: Arc::new(gen_input),
+            gen_state: Arc::new(gen_state),
+            call: Arc::new(call),
+            options: CampaignOptions::new(),
         }
     }
 
+    /// Limits of the runs, none by default
+    pub fn with_options(self, options: CampaignOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Run the mutations of indexes `mutations` on one source input
+    #[allow(clippy::too_many_arguments)]
     fn run_mutator<T: Mutator<Input, State>>(
         &self,
         initial_state: &State,
         initial_input: &Input,
         reference_output: &Output,
         mutator: &T,
-        max_input_size: usize,
-        collect_outputs: bool,
-    ) -> (Option<Vec<(Input, Output, usize)>>, Option<Vec<Output>>) {
-        let mut collected_outputs = vec![];
+        check: &(impl Fn(&Output, &Output) -> bool + Sync),
+        mutations: impl Iterator<Item = usize>,
+        findings: &AtomicUsize,
+    ) -> (Vec<(Input, Output, usize)>, usize) {
         let mut collected_errors = vec![];
-        for size in 1..max_input_size {
-            let (mutated_state, mutated_input) =
-                mutator.mutate_input(initial_input, initial_state, size);
-            let new_output = (self.call)(mutated_state, &mutated_input);
-            if collect_outputs {
-                collected_outputs.push(new_output.clone());
+        let mut ran = 0;
+        // Reused by the mutators with an in-place path, restored after each call
+        let mut working_input = initial_input.clone();
+        for element in mutations {
+            if self.options.exhausted(findings.load(Ordering::Relaxed)) {
+                break;
             }
-            if !(self.check)(&new_output, reference_output) {
-                collected_errors.push((mutated_input, new_output, size));
+            ran += 1;
+            let error = if mutator.mutate_in_place(&mut working_input, element) {
+                let new_output = (self.call)(initial_state.clone(), &working_input);
+                let error = (!check(&new_output, reference_output))
+                    .then(|| (working_input.clone(), new_output, element));
+                mutator.undo_in_place(&mut working_input, element);
+                error
+            } else {
+                let (mutated_state, mutated_input) =
+                    mutator.mutate_input(initial_input, initial_state, element);
+                let new_output = (self.call)(mutated_state, &mutated_input);
+                (!check(&new_output, reference_output)).then_some((
+                    mutated_input,
+                    new_output,
+                    element,
+                ))
+            };
+            if let Some(error) = error {
+                // Parallel chunks can find errors past the cutoff, only the first ones are kept
+                let previous = findings.fetch_add(1, Ordering::Relaxed);
+                if self.options.max_findings.is_none_or(|max| previous < max) {
+                    collected_errors.push(error);
+                }
             }
         }
+        (collected_errors, ran)
+    }
 
-        let errors = if !collected_errors.is_empty() {
-            Some(collected_errors)
-        } else {
-            None
-        };
-
-        let outputs = if collect_outputs {
-            Some(collected_outputs)
-        } else {
-            None
-        };
-
-        (errors, outputs)
+    /// Run a relation: its source transformation is applied to every generated input, its
+    /// follow-up mutator derives the follow-up inputs and its predicate checks the outputs
+    pub fn run_relation<R: MetamorphicRelation<State, Input, Output>>(
+        &self,
+        min_size: usize,
+        max_size: usize,
+        lib_name: &str,
+        relation: &R,
+    ) -> usize {
+        self.run(
+            min_size,
+            max_size,
+            relation.name(),
+            lib_name,
+            relation.follow_up(),
+            |input| relation.source(input),
+            |output, reference_output| relation.holds(output, reference_output),
+        )
     }
 
-    pub fn run_test<T: Mutator<Input, State> + Send + Sync>(
+    #[allow(clippy::too_many_arguments)]
+    fn run<T: Mutator<Input, State> + Send + Sync>(
         &self,
         min_size: usize,
         max_size: usize,
         test_name: &str,
         lib_name: &str,
         mutator: T,
-    ) {
-        let shared_mutator = Arc::new(mutator.clone());
-        let number_of_errors :usize = (min_size..max_size+1)
+        source: impl Fn(Input) -> Input + Sync,
+        check: impl Fn(&Output, &Output) -> bool + Sync,
+    ) -> usize {
+        let lib_name = &self.options.scoped(lib_name);
+        let findings = AtomicUsize::new(0);
+        let covered = AtomicUsize::new(0);
+        let number_of_errors: usize = (min_size..max_size + 1)
             .into_par_iter()
             .map(|size| {
-                let input = (self.gen_input)(size);
+                let checkpoint = self.options.checkpoint.as_deref();
+                let key = |chunk| {
+                    (
+                        lib_name.to_string(),
+                        test_name.to_string(),
+                        min_size,
+                        max_size,
+                        size,
+                        self.options.sampled_mutations,
+                        chunk,
+                    )
+                };
+                // A chunk recorded by an earlier run of the campaign is not run again, its errors
+                // are printed from the checkpoint
+                let replay = |chunk| {
+                    let record = checkpoint?.get(&key(chunk))?;
+                    for (position, seed) in &record.findings {
+                        println!(
+                            "[{}] ! ERROR ! Running {} on size {} (impacted bit: {}{}): recorded in the checkpoint",
+                            lib_name,
+                            test_name,
+                            size,
+                            position,
+                            seed.map(|seed| format!(", seed: {}", seed))
+                                .unwrap_or_default()
+                        );
+                    }
+                    covered.fetch_add(record.ran, Ordering::Relaxed);
+                    findings.fetch_add(record.findings.len(), Ordering::Relaxed);
+                    Some(record.findings.len())
+                };
+                let chunks = self
+                    .options
+                    .position_count(size)
+                    .div_ceil(MUTATION_CHUNK_SIZE);
+                if checkpoint.is_some_and(|checkpoint| {
+                    (0..chunks).all(|chunk| checkpoint.get(&key(chunk)).is_some())
+                }) {
+                    return (0..chunks).filter_map(replay).sum();
+                }
+                if self.options.exhausted(findings.load(Ordering::Relaxed)) {
+                    if let Some(checkpoint) = checkpoint {
+                        checkpoint.mark_incomplete();
+                    }
+                    return (0..chunks).filter_map(replay).sum();
+                }
+                let mut rng =
+                    StdRng::seed_from_u64(self.options.size_seed(lib_name, test_name, size));
+                // The source input is derived from the campaign seed, so that a resumed size runs
+                // its remaining chunks on the input of the interrupted run
+                reseed_campaign_rng(rng.next_u64());
+                let input = source((self.gen_input)(size));
                 println!(
                     "[{}] Running {} with {} bytes input size",
                     lib_name, test_name, size
                 );
-                let new_mutator = Arc::clone(&shared_mutator);
-                let ref_output = (self.call)((self.gen_state)(), &input);
-                let (errors, _) = self.run_mutator(
-                    &(self.gen_state)(),
-                    &input,
-                    &ref_output,
-                    new_mutator.deref(),
-                    size,
-                    false,
-                );
-
-                match errors {
-                    Some(err) =>  {
-                        for e in &err {
+                let initial_state = (self.gen_state)();
+                let ref_output = (self.call)(initial_state.clone(), &input);
+                let positions = self.options.positions(size, &mut rng);
+                let mutator = mutator.clone().reseed(rng.next_u64());
+                // Mutations of one size are split in chunks scheduled as separate tasks, so that a
+                // single large size also runs on every core
+                positions
+                    .par_chunks(MUTATION_CHUNK_SIZE)
+                    .enumerate()
+                    .map(|(chunk_index, chunk)| {
+                        if let Some(errors) = replay(chunk_index) {
+                            return errors;
+                        }
+                        let (errors, ran) = self.run_mutator(
+                            &initial_state,
+                            &input,
+                            &ref_output,
+                            &mutator,
+                            &check,
+                            chunk.iter().copied(),
+                            &findings,
+                        );
+                        covered.fetch_add(ran, Ordering::Relaxed);
+                        for e in &errors {
                             println!(
-                                "[{}] ! ERROR ! Running {} on size {} (impacted bit: {}): reference input {:?}, reference output {:?}, input {:?}, output {:?}",
-                                lib_name, test_name, size, e.2, input, ref_output, e.0, e.1
+                                "[{}] ! ERROR ! Running {} on size {} (impacted bit: {}{}): reference input {:?}, reference output {:?}, input {:?}, output {:?}",
+                                lib_name,
+                                test_name,
+                                size,
+                                e.2,
+                                mutator
+                                    .seed(e.2)
+                                    .map(|seed| format!(", seed: {}", seed))
+                                    .unwrap_or_default(),
+                                input,
+                                ref_output,
+                                e.0,
+                                e.1
                             );
                         }
-                        err.len()
-                    },
-                    None => 0,
-                }
-            }).sum();
+                        // Chunks cut by the time budget or the findings cutoff are run again on
+                        // resume
+                        if let Some(checkpoint) = checkpoint {
+                            let record = CheckpointRecord {
+                                ran,
+                                findings: errors.iter().map(|e| (e.2, mutator.seed(e.2))).collect(),
+                            };
+                            if ran < chunk.len() {
+                                checkpoint.mark_incomplete();
+                            } else if let Err(error) =
+                                checkpoint.record(&key(chunk_index), &record)
+                            {
+                                checkpoint.mark_incomplete();
+                                println!(
+                                    "[{}] ! CHECKPOINT ! Cannot record {} on size {}, chunk {}: {}",
+                                    lib_name, test_name, size, chunk_index, error
+                                );
+                            }
+                        }
+                        errors.len()
+                    })
+                    .sum::<usize>()
+            })
+            .sum();
+
+        let mutations: usize = (min_size..max_size + 1)
+            .map(|size| size.saturating_sub(1))
+            .sum();
+        let covered = covered.into_inner();
+        if covered < mutations && self.options.deadline_passed() {
+            println!(
+                "[{}] !SUMMARY ! {} with sizes [{}-{}] : cut by the time budget after {}/{} mutations, found {} errors",
+                lib_name, test_name, min_size, max_size, covered, mutations, number_of_errors
+            );
+            return number_of_errors;
+        }
+        println!(
+            "[{}] !SUMMARY ! {} with sizes [{}-{}] : found {} errors",
+            lib_name, test_name, min_size, max_size, number_of_errors
+        );
+        if covered < mutations {
+            println!(
+                "[{}] !COVERAGE ! {} ran {}/{} mutations ({:.2}%)",
+                lib_name,
+                test_name,
+                covered,
+                mutations,
+                covered as f64 * 100.0 / mutations as f64
+            );
+        }
+        number_of_errors
+    }
+}
+
+/// Target along with a fixed predicate, for runs of ad-hoc mutators
+pub struct MetamorphicTestRunner<State, Input, Output> {
+    target: MetamorphicTarget<State, Input, Output>,
+    /// Check if the output match the condition
+    check: CheckFn<Output>,
+}
+
+impl<
+        State: Clone + Send + Sync,
+        Input: std::fmt::Debug + Send + Sync + Clone,
+        Output: std::fmt::Debug + Send + Sync + Clone,
+    > MetamorphicTestRunner<State, Input, Output>
+{
+    pub fn new(
+        gen_input: impl Fn(usize) -> Input + Send + Sync + 'static,
+        gen_state: impl Fn() -> State + Send + Sync + 'static,
+        call: impl Fn(State, &Input) -> Output + Send + Sync + 'static,
+        check: impl Fn(&Output, &Output) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            target: MetamorphicTarget::new(gen_input, gen_state, call),
+            check: Arc::new(check),
+        }
+    }
+
+    pub fn with_options(self, options: CampaignOptions) -> Self {
+        Self {
+            target: self.target.with_options(options),
+            ..self
+        }
+    }
+
+    pub fn run_test<T: Mutator<Input, State> + Send + Sync>(
+        &self,
+        min_size: usize,
+        max_size: usize,
+        test_name: &str,
+        lib_name: &str,
+        mutator: T,
+    ) -> usize {
+        self.target.run(
+            min_size,
+            max_size,
+            test_name,
+            lib_name,
+            mutator,
+            |input| input,
+            self.check.as_ref(),
+        )
+    }
+}
+
+/// Check run on the output of a follow-up input and the output of its source input
+pub trait OutputPredicate<O>: Clone + Send + Sync {
+    fn holds(&self, output: &O, reference_output: &O) -> bool;
+
+    fn and<Q: OutputPredicate<O>>(self, other: Q) -> And<Self, Q> {
+        And(self, other)
+    }
+
+    fn or<Q: OutputPredicate<O>>(self, other: Q) -> Or<Self, Q> {
+        Or(self, other)
+    }
+}
+
+impl<O, F: Fn(&O, &O) -> bool + Clone + Send + Sync> OutputPredicate<O> for F {
+    fn holds(&self, output: &O, reference_output: &O) -> bool {
+        self(output, reference_output)
+    }
+}
+
+#[derive(Clone)]
+pub struct And<P, Q>(P, Q);
+
+impl<O, P: OutputPredicate<O>, Q: OutputPredicate<O>> OutputPredicate<O> for And<P, Q> {
+    fn holds(&self, output: &O, reference_output: &O) -> bool {
+        self.0.holds(output, reference_output) && self.1.holds(output, reference_output)
+    }
+}
+
+#[derive(Clone)]
+pub struct Or<P, Q>(P, Q);
+
+impl<O, P: OutputPredicate<O>, Q: OutputPredicate<O>> OutputPredicate<O> for Or<P, Q> {
+    fn holds(&self, output: &O, reference_output: &O) -> bool {
+        self.0.holds(output, reference_output) || self.1.holds(output, reference_output)
+    }
+}
+
+pub fn outputs_equal<O: PartialEq>(output: &O, reference_output: &O) -> bool {
+    output == reference_output
+}
+
+pub fn outputs_differ<O: PartialEq>(output: &O, reference_output: &O) -> bool {
+    output != reference_output
+}
+
+/// A source input transformation, a follow-up input transformation and a predicate relating the
+/// outputs of both, independent of the target it is applied to
+pub trait MetamorphicRelation<S: Clone, I: Clone, O>: Send + Sync {
+    type FollowUp: Mutator<I, S> + Send + Sync;
+
+    fn name(&self) -> &str;
+
+    /// Transformation applied to every generated source input
+    fn source(&self, input: I) -> I {
+        input
+    }
+
+    fn follow_up(&self) -> Self::FollowUp;
+    fn holds(&self, output: &O, reference_output: &O) -> bool;
+}
+
+#[derive(Clone)]
+pub struct Relation<I, M, P> {
+    name: &'static str,
+    source: Arc<dyn Fn(I) -> I + Send + Sync>,
+    follow_up: M,
+    predicate: P,
+}
+
+impl<I, M, P> Relation<I, M, P> {
+    pub fn new(name: &'static str, follow_up: M, predicate: P) -> Self {
+        Self {
+            name,
+            source: Arc::new(|input| input),
+            follow_up,
+            predicate,
+        }
+    }
+
+    /// Transform every generated source input after the transformations set before, the closure
+    /// may capture its configuration
+    pub fn with_source(self, source: impl Fn(I) -> I + Send + Sync + 'static) -> Self
+    where
+        I: 'static,
+    {
+        let previous = self.source;
+        Self {
+            source: Arc::new(move |input| source(previous(input))),
+            ..self
+        }
+    }
+
+    /// Apply another mutator on the follow-up inputs
+    pub fn then<S: Clone, N: Mutator<I, S>>(self, mutator: N) -> Relation<I, Compose<M, N>, P>
+    where
+        I: Clone,
+        M: Mutator<I, S>,
+    {
+        Relation {
+            name: self.name,
+            source: self.source,
+            follow_up: self.follow_up.then(mutator),
+            predicate: self.predicate,
+        }
+    }
+
+    pub fn and<O, Q: OutputPredicate<O>>(self, predicate: Q) -> Relation<I, M, And<P, Q>>
+    where
+        P: OutputPredicate<O>,
+    {
+        Relation {
+            name: self.name,
+            source: self.source,
+            follow_up: self.follow_up,
+            predicate: self.predicate.and(predicate),
+        }
+    }
+
+    pub fn or<O, Q: OutputPredicate<O>>(self, predicate: Q) -> Relation<I, M, Or<P, Q>>
+    where
+        P: OutputPredicate<O>,
+    {
+        Relation {
+            name: self.name,
+            source: self.source,
+            follow_up: self.follow_up,
+            predicate: self.predicate.or(predicate),
+        }
+    }
+}
+
+impl<S, I, O, M, P> MetamorphicRelation<S, I, O> for Relation<I, M, P>
+where
+    S: Clone,
+    I: Clone,
+    M: Mutator<I, S> + Send + Sync,
+    P: OutputPredicate<O>,
+{
+    type FollowUp = M;
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn source(&self, input: I) -> I {
+        (self.source)(input)
+    }
+
+    fn follow_up(&self) -> M {
+        self.follow_up.clone()
+    }
+
+    fn holds(&self, output: &O, reference_output: &O) -> bool {
+        self.predicate.holds(output, reference_output)
+    }
+}
+
+/// Digests of the two forked states and of the original state, each with its one-shot digest
+pub type ForkOutput<O> = ((O, O), (O, O), (O, O));
+
+pub trait HashMetamorphic: 'static {
+    type Input: std::fmt::Debug + Clone + Send + Sync + 'static;
+    type Output: std::fmt::Debug + Clone + Send + Sync + PartialEq;
+    type State: Clone + Send + Sync;
 
+    const LIBNAME: &str;
+
+    fn initial_state() -> Self::State;
+    fn gen_input(size: usize) -> Self::Input;
+    fn input_as_u8(input: &Self::Input) -> Vec<u8>;
+
+    /// Return unmodified initial state and mutated as input
+    fn u8_as_input(
+        initial_state: &Self::State,
+        _initial_input: &Self::Input,
+        mutated: Vec<u8>,
+    ) -> (Self::State, Self::Input);
+
+    fn hash_update(
+        initial_state: &Self::State,
+        _initial_input: &Self::Input,
+        first_part: &[u8],
+        second_part: &[u8],
+    ) -> (Self::State, Self::Input);
+
+    /// Mutable bytes of the input, when the input stores them as is, to flip its bits in place:
+    /// `Vec<u8>` inputs are supported by default, other inputs go through `u8_as_input`
+    fn input_bytes_mut(input: &mut Self::Input) -> Option<&mut [u8]> {
+        (input as &mut dyn std::any::Any)
+            .downcast_mut::<Vec<u8>>()
+            .map(Vec::as_mut_slice)
+    }
+
+    fn output_as_u8(output: &Self::Output) -> Vec<u8>;
+    fn compare_output(initial_output: &Self::Output, output: &Self::Output) -> bool;
+    fn hash(state: Self::State, input: &Self::Input) -> Self::Output;
+
+    /// Digests of the message computed through every other API of the target (one-shot
+    /// helpers, chained updates, reset and reuse, `io::Write`, ...)
+    fn hash_api_paths(_message: &[u8]) -> Vec<Vec<u8>> {
+        vec![]
+    }
+
+    /// Digest through `new` + `update` + `finalize`, and through every other API path
+    fn call_test_api_paths(state: Self::State, input: &Self::Input) -> (Vec<u8>, Vec<Vec<u8>>) {
+        (
+            Self::output_as_u8(&Self::hash(state, input)),
+            Self::hash_api_paths(&Self::input_as_u8(input)),
+        )
+    }
+
+    /// Run any relation on the target, with inputs of 1 to `max_size` bytes
+    fn check_relation(
+        relation: &impl MetamorphicRelation<Self::State, Self::Input, Self::Output>,
+        max_size: usize,
+        options: &CampaignOptions,
+    ) -> usize {
+        MetamorphicTarget::new(Self::gen_input, Self::initial_state, Self::hash)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, relation)
+    }
+
+    /// Flipping any input bit changes the digest
+    fn bit_inclusion_relation() -> impl MetamorphicRelation<Self::State, Self::Input, Self::Output>
+    {
+        Relation::new(
+            "Bit Inclusion",
+            BitInclusionMutator::new(Self::input_as_u8, Self::u8_as_input)
+                .with_bytes_mut(Self::input_bytes_mut),
+            outputs_differ,
+        )
+    }
+
+    /// Absorbing the input in two updates gives the one-shot digest
+    fn update_hash_relation() -> impl MetamorphicRelation<Self::State, Self::Input, Self::Output> {
+        Relation::new(
+            "Update Hash",
+            UpdateMutator::new(Self::input_as_u8, Self::hash_update),
+            outputs_equal,
+        )
+    }
+
+    /// Flipping any input bit changes the digest, also when the flipped input is absorbed in two
+    /// updates split at the flipped position
+    fn bit_inclusion_update_relation(
+    ) -> impl MetamorphicRelation<Self::State, Self::Input, Self::Output> {
+        Relation::new(
+            "Bit Inclusion with Update",
+            BitInclusionMutator::new(Self::input_as_u8, Self::u8_as_input),
+            outputs_differ,
+        )
+        .then(UpdateMutator::new(Self::input_as_u8, Self::hash_update))
+    }
+
+    fn bit_inclusion_test(max_size: usize) -> usize {
+        Self::bit_inclusion_test_with_options(max_size, &CampaignOptions::default())
+    }
+
+    fn bit_inclusion_test_with_options(max_size: usize, options: &CampaignOptions) -> usize {
+        Self::check_relation(&Self::bit_inclusion_relation(), max_size, options)
+    }
+
+    fn update_hash_test(max_size: usize) -> usize {
+        Self::update_hash_test_with_options(max_size, &CampaignOptions::default())
+    }
+
+    fn update_hash_test_with_options(max_size: usize, options: &CampaignOptions) -> usize {
+        Self::check_relation(&Self::update_hash_relation(), max_size, options)
+    }
+
+    fn bit_inclusion_update_test(max_size: usize, options: &CampaignOptions) -> usize {
+        Self::check_relation(&Self::bit_inclusion_update_relation(), max_size, options)
+    }
+
+    fn gen_fork_input(size: usize) -> (Self::Input, usize) {
+        (Self::gen_input(size), 0)
+    }
+
+    fn set_fork_position(
+        initial_state: &Self::State,
+        input: &(Self::Input, usize),
+        element_to_mutate: usize,
+    ) -> (Self::State, (Self::Input, usize)) {
+        (initial_state.clone(), (input.0.clone(), element_to_mutate))
+    }
+
+    /// Absorb a prefix, clone the state, continue both clones with different suffixes then with
+    /// the same suffix, interleaving the updates of the two clones, and finalize the original
+    /// state with that suffix only. Each digest is returned along with its one-shot counterpart.
+    fn call_test_fork(
+        initial_state: Self::State,
+        input: &(Self::Input, usize),
+    ) -> ForkOutput<Self::Output> {
+        let bytes = Self::input_as_u8(&input.0);
+        let (prefix, rest) = bytes.split_at(input.1 % (bytes.len() + 1));
+        let suffix_a = rest.to_vec();
+        let suffix_b: Vec<u8> = rest.iter().map(|byte| !byte).collect();
+
+        let (state, _) = Self::hash_update(&initial_state, &input.0, prefix, &[]);
+        let (fork_a, _) = Self::hash_update(&state, &input.0, &suffix_a, &[]);
+        let (fork_b, _) = Self::hash_update(&state, &input.0, &suffix_b, &[]);
+        let (fork_a, rest_a) = Self::hash_update(&fork_a, &input.0, &bytes, &[]);
+        let (fork_b, rest_b) = Self::hash_update(&fork_b, &input.0, &bytes, &[]);
+        let digest_a = Self::hash(fork_a, &rest_a);
+        let digest_b = Self::hash(fork_b, &rest_b);
+        let (_, common) = Self::u8_as_input(&initial_state, &input.0, bytes.clone());
+        let digest = Self::hash(state, &common);
+
+        let one_shot = |message: Vec<u8>| {
+            let (state, message) = Self::u8_as_input(&initial_state, &input.0, message);
+            Self::hash(state, &message)
+        };
+        (
+            (digest_a, one_shot([prefix, &suffix_a, &bytes].concat())),
+            (digest_b, one_shot([prefix, &suffix_b, &bytes].concat())),
+            (digest, one_shot([prefix, &bytes].concat())),
+        )
+    }
+
+    /// Cloned mid-stream states must be independent and match the one-shot digests
+    fn fork_relation(
+    ) -> impl MetamorphicRelation<Self::State, (Self::Input, usize), ForkOutput<Self::Output>> {
+        Relation::new(
+            "State fork",
+            IndexMutator::new(Self::set_fork_position),
+            |output: &ForkOutput<Self::Output>, _reference_output: &ForkOutput<Self::Output>| {
+                output.0 .0 == output.0 .1
+                    && output.1 .0 == output.1 .1
+                    && output.2 .0 == output.2 .1
+            },
+        )
+    }
+
+    fn fork_test(max_size: usize, options: &CampaignOptions) -> usize {
+        MetamorphicTarget::new(
+            Self::gen_fork_input,
+            Self::initial_state,
+            Self::call_test_fork,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::fork_relation())
+    }
+
+    /// Hashing the message from every byte offset of a larger allocation must give the aligned
+    /// one-shot digest
+    fn alignment_relation() -> impl MetamorphicRelation<Self::State, Self::Input, Self::Output> {
+        Relation::new(
+            "Misaligned buffers",
+            AlignmentMutator::new(Self::input_as_u8, Self::hash_update),
+            outputs_equal,
+        )
+    }
+
+    fn alignment_test(max_size: usize, options: &CampaignOptions) -> usize {
+        Self::check_relation(&Self::alignment_relation(), max_size, options)
+    }
+
+    /// Feeding the message through `io::copy` from a source with random short reads must give
+    /// the one-shot digest
+    fn short_read_relation() -> impl MetamorphicRelation<Self::State, Self::Input, Self::Output> {
+        Relation::new(
+            "Short reads",
+            ShortReadMutator::new(Self::input_as_u8, Self::hash_update),
+            outputs_equal,
+        )
+    }
+
+    fn short_read_test(max_size: usize, options: &CampaignOptions) -> usize {
+        Self::check_relation(&Self::short_read_relation(), max_size, options)
+    }
+
+    /// All API paths must agree on every message
+    fn api_paths_relation(
+    ) -> impl MetamorphicRelation<Self::State, Self::Input, (Vec<u8>, Vec<Vec<u8>>)> {
+        Relation::new(
+            "API paths",
+            BitInclusionMutator::new(Self::input_as_u8, Self::u8_as_input),
+            |output: &(Vec<u8>, Vec<Vec<u8>>), _reference_output: &(Vec<u8>, Vec<Vec<u8>>)| {
+                output.1.iter().all(|digest| *digest == output.0)
+            },
+        )
+    }
+
+    fn api_paths_test(max_size: usize, options: &CampaignOptions) -> usize {
+        MetamorphicTarget::new(
+            Self::gen_input,
+            Self::initial_state,
+            Self::call_test_api_paths,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::api_paths_relation())
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::bit_inclusion_test_with_options(max_size, options);
+        Self::update_hash_test_with_options(max_size, options);
+        Self::bit_inclusion_update_test(max_size, options);
+        Self::fork_test(max_size, options);
+        Self::alignment_test(max_size, options);
+        Self::short_read_test(max_size, options);
+        if !Self::hash_api_paths(&[]).is_empty() {
+            Self::api_paths_test(max_size, options);
+        }
+    }
+}
+
+/// Hash absorbing its input in fixed-size blocks, the fault injection of the mutation analysis
+/// splits the input on them
+pub trait BlockHash: HashMetamorphic {
+    /// Size in bytes of the blocks processed by the compression function
+    const BLOCK_SIZE: usize;
+}
+
+/// Key, message and tag
+pub type MacInput = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+pub trait MacMetamorphic: 'static {
+    /// Keyed streaming context
+    type State: Clone + Send + Sync;
+
+    const LIBNAME: &str;
+    const KEYSIZE: usize;
+    const TAGSIZE: usize;
+
+    fn new_state(key: &[u8]) -> Self::State;
+    fn update(state: &mut Self::State, data: &[u8]);
+    fn finalize(state: Self::State) -> Vec<u8>;
+    fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool;
+
+    /// One-shot tag computation, defaults to the streaming API
+    fn mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut state = Self::new_state(key);
+        Self::update(&mut state, message);
+        Self::finalize(state)
+    }
+
+    /// Random key, random message of `size` bytes and its tag
+    fn gen_input(size: usize) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let mut key = vec![0u8; Self::KEYSIZE];
+        let mut message = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut key);
+        campaign_rng().fill_bytes(&mut message);
+        let tag = Self::mac(&key, &message);
+        (key, message, tag)
+    }
+
+    /// No state means a one-shot tag, otherwise the state is keyed and continued with the message
+    fn gen_state() -> Option<Self::State> {
+        None
+    }
+
+    fn call_test_mac(state: Option<Self::State>, input: &(Vec<u8>, Vec<u8>, Vec<u8>)) -> Vec<u8> {
+        match state {
+            Some(mut state) => {
+                Self::update(&mut state, &input.1);
+                Self::finalize(state)
+            }
+            None => Self::mac(&input.0, &input.1),
+        }
+    }
+
+    fn call_test_verify(_state: Option<Self::State>, input: &(Vec<u8>, Vec<u8>, Vec<u8>)) -> bool {
+        Self::verify(&input.0, &input.1, &input.2)
+    }
+
+    fn get_key_from_input_as_u8(input: &(Vec<u8>, Vec<u8>, Vec<u8>)) -> Vec<u8> {
+        input.0.clone()
+    }
+
+    fn set_key_from_input_as_u8(
+        state: &Option<Self::State>,
+        input: &(Vec<u8>, Vec<u8>, Vec<u8>),
+        key: Vec<u8>,
+    ) -> (Option<Self::State>, MacInput) {
+        (state.clone(), (key, input.1.clone(), input.2.clone()))
+    }
+
+    fn get_message_from_input_as_u8(input: &(Vec<u8>, Vec<u8>, Vec<u8>)) -> Vec<u8> {
+        input.1.clone()
+    }
+
+    fn set_message_from_input_as_u8(
+        state: &Option<Self::State>,
+        input: &(Vec<u8>, Vec<u8>, Vec<u8>),
+        message: Vec<u8>,
+    ) -> (Option<Self::State>, MacInput) {
+        (state.clone(), (input.0.clone(), message, input.2.clone()))
+    }
+
+    fn get_tag_from_input_as_u8(input: &(Vec<u8>, Vec<u8>, Vec<u8>)) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_tag_from_input_as_u8(
+        state: &Option<Self::State>,
+        input: &(Vec<u8>, Vec<u8>, Vec<u8>),
+        tag: Vec<u8>,
+    ) -> (Option<Self::State>, MacInput) {
+        (state.clone(), (input.0.clone(), input.1.clone(), tag))
+    }
+
+    fn mac_update(
+        _initial_state: &Option<Self::State>,
+        input: &(Vec<u8>, Vec<u8>, Vec<u8>),
+        first_part: &[u8],
+        second_part: &[u8],
+    ) -> (Option<Self::State>, MacInput) {
+        let mut state = Self::new_state(&input.0);
+        Self::update(&mut state, first_part);
+        (
+            Some(state),
+            (input.0.clone(), second_part.to_vec(), input.2.clone()),
+        )
+    }
+
+    fn bit_inclusion_on_key_relation(
+    ) -> impl MetamorphicRelation<Option<Self::State>, (Vec<u8>, Vec<u8>, Vec<u8>), Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on key",
+            BitInclusionMutator::new(
+                Self::get_key_from_input_as_u8,
+                Self::set_key_from_input_as_u8,
+            ),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_key_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_mac)
+            .with_options(options.clone())
+            .run_relation(
+                Self::KEYSIZE * 8,
+                Self::KEYSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_key_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_message_relation(
+    ) -> impl MetamorphicRelation<Option<Self::State>, (Vec<u8>, Vec<u8>, Vec<u8>), Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on message",
+            BitInclusionMutator::new(
+                Self::get_message_from_input_as_u8,
+                Self::set_message_from_input_as_u8,
+            ),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_message_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_mac)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_message_relation(),
+            );
+    }
+
+    /// Streaming a split message must give the one-shot tag
+    fn update_mac_relation(
+    ) -> impl MetamorphicRelation<Option<Self::State>, (Vec<u8>, Vec<u8>, Vec<u8>), Vec<u8>> {
+        Relation::new(
+            "Update MAC",
+            UpdateMutator::new(Self::get_message_from_input_as_u8, Self::mac_update),
+            outputs_equal,
+        )
+    }
+
+    fn update_mac_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_mac)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::update_mac_relation());
+    }
+
+    /// Verification must reject any flipped tag bit
+    fn tag_rejection_relation(
+    ) -> impl MetamorphicRelation<Option<Self::State>, (Vec<u8>, Vec<u8>, Vec<u8>), bool> {
+        Relation::new(
+            "Tag rejection",
+            BitInclusionMutator::new(
+                Self::get_tag_from_input_as_u8,
+                Self::set_tag_from_input_as_u8,
+            ),
+            |output: &bool, reference_output: &bool| *reference_output && !output,
+        )
+    }
+
+    fn tag_rejection_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_verify)
+            .with_options(options.clone())
+            .run_relation(
+                Self::TAGSIZE * 8,
+                Self::TAGSIZE * 8,
+                Self::LIBNAME,
+                &Self::tag_rejection_relation(),
+            );
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::bit_inclusion_on_key_test(options);
+        Self::bit_inclusion_on_message_test(max_size, options);
+        Self::update_mac_test(max_size, options);
+        Self::tag_rejection_test(options);
+    }
+}
+
+/// Salt, input key material, context (info) and output size
+pub type KdfInput = (Vec<u8>, Vec<u8>, Vec<u8>, usize);
+
+pub trait KdfMetamorphic: 'static {
+    const LIBNAME: &str;
+    /// Salt size, 0 when the KDF takes no salt
+    const SALTSIZE: usize;
+    const INFOSIZE: usize;
+    const MAX_OUTPUT_SIZE: usize;
+    /// Number of context mutations run by the bit inclusion on info relation
+    const INFO_MUTATIONS: usize = Self::INFOSIZE * 8;
+
+    fn derive(salt: &[u8], ikm: &[u8], info: &[u8], output_size: usize) -> Vec<u8>;
+
+    fn gen_info() -> Vec<u8> {
+        let mut info = vec![0u8; Self::INFOSIZE];
+        campaign_rng().fill_bytes(&mut info);
+        info
+    }
+
+    /// Random salt and context, input key material and output of `size` bytes
+    fn gen_input(size: usize) -> KdfInput {
+        let mut salt = vec![0u8; Self::SALTSIZE];
+        let mut ikm = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut salt);
+        campaign_rng().fill_bytes(&mut ikm);
+        (salt, ikm, Self::gen_info(), size.min(Self::MAX_OUTPUT_SIZE))
+    }
+
+    fn gen_state() {}
+
+    fn call_test(_state: (), input: &KdfInput) -> Vec<u8> {
+        Self::derive(&input.0, &input.1, &input.2, input.3)
+    }
+
+    fn get_salt_from_input_as_u8(input: &KdfInput) -> Vec<u8> {
+        input.0.clone()
+    }
+
+    fn set_salt_from_input_as_u8(_state: &(), input: &KdfInput, salt: Vec<u8>) -> ((), KdfInput) {
+        ((), (salt, input.1.clone(), input.2.clone(), input.3))
+    }
+
+    fn get_info_from_input_as_u8(input: &KdfInput) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_info_from_input_as_u8(_state: &(), input: &KdfInput, info: Vec<u8>) -> ((), KdfInput) {
+        ((), (input.0.clone(), input.1.clone(), info, input.3))
+    }
+
+    /// Apply the `element_to_mutate`-th context mutation, a bit flip by default
+    fn mutate_info(info: &mut [u8], element_to_mutate: usize) {
+        flip_one_bit(info, element_to_mutate);
+    }
+
+    fn set_mutated_info_from_input(
+        _state: &(),
+        input: &KdfInput,
+        element_to_mutate: usize,
+    ) -> ((), KdfInput) {
+        let mut info = input.2.clone();
+        Self::mutate_info(&mut info, element_to_mutate);
+        ((), (input.0.clone(), input.1.clone(), info, input.3))
+    }
+
+    /// Only the split position matters, the output size is encoded as a dummy byte string
+    fn get_output_size_from_input_as_u8(input: &KdfInput) -> Vec<u8> {
+        vec![0u8; input.3]
+    }
+
+    fn set_output_size_from_input(
+        _state: &(),
+        input: &KdfInput,
+        first_part: &[u8],
+        _second_part: &[u8],
+    ) -> ((), KdfInput) {
+        (
+            (),
+            (
+                input.0.clone(),
+                input.1.clone(),
+                input.2.clone(),
+                first_part.len(),
+            ),
+        )
+    }
+
+    /// Keep the salt and input key material, truncate the context to its first part
+    fn truncate_info(
+        _state: &(),
+        input: &KdfInput,
+        first_part: &[u8],
+        _second_part: &[u8],
+    ) -> ((), KdfInput) {
+        (
+            (),
+            (
+                input.0.clone(),
+                input.1.clone(),
+                first_part.to_vec(),
+                input.3,
+            ),
+        )
+    }
+
+    /// Deriving n bytes must give a prefix of deriving m > n bytes
+    fn prefix_relation() -> impl MetamorphicRelation<(), KdfInput, Vec<u8>> {
+        Relation::new(
+            "KDF Prefix",
+            UpdateMutator::new(
+                Self::get_output_size_from_input_as_u8,
+                Self::set_output_size_from_input,
+            ),
+            |output: &Vec<u8>, reference_output: &Vec<u8>| reference_output.starts_with(output),
+        )
+    }
+
+    /// The output size follows the input size, sizes past `MAX_OUTPUT_SIZE` are not run
+    fn prefix_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size.min(Self::MAX_OUTPUT_SIZE),
+                Self::LIBNAME,
+                &Self::prefix_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_info_relation() -> impl MetamorphicRelation<(), KdfInput, Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on info",
+            IndexMutator::new(Self::set_mutated_info_from_input),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_info_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test)
+            .with_options(options.clone())
+            .run_relation(
+                Self::INFO_MUTATIONS,
+                Self::INFO_MUTATIONS,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_info_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_salt_relation() -> impl MetamorphicRelation<(), KdfInput, Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on salt",
+            BitInclusionMutator::new(
+                Self::get_salt_from_input_as_u8,
+                Self::set_salt_from_input_as_u8,
+            ),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_salt_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test)
+            .with_options(options.clone())
+            .run_relation(
+                Self::SALTSIZE * 8,
+                Self::SALTSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_salt_relation(),
+            );
+    }
+
+    /// Two different context strings, one a prefix of the other, must derive different keys
+    /// from the same input key material
+    fn domain_separation_relation() -> impl MetamorphicRelation<(), KdfInput, Vec<u8>> {
+        Relation::new(
+            "Domain separation",
+            UpdateMutator::new(Self::get_info_from_input_as_u8, Self::truncate_info),
+            outputs_differ,
+        )
+    }
+
+    fn domain_separation_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test)
+            .with_options(options.clone())
+            .run_relation(
+                Self::INFOSIZE,
+                Self::INFOSIZE,
+                Self::LIBNAME,
+                &Self::domain_separation_relation(),
+            );
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::prefix_test(max_size, options);
+        Self::bit_inclusion_on_info_test(options);
+        if Self::SALTSIZE > 0 {
+            Self::bit_inclusion_on_salt_test(options);
+        }
+        Self::domain_separation_test(options);
+    }
+}
+
+/// Password, salt and cost parameters
+pub type PasswordHashInput<P> = (Vec<u8>, Vec<u8>, P);
+
+pub trait PasswordHashMetamorphic: 'static {
+    type Params: std::fmt::Debug + Clone + Send + Sync;
+
+    const LIBNAME: &str;
+    const SALTSIZE: usize;
+    /// Number of cost parameters that `mutate_params` can change
+    const PARAMS_COUNT: usize;
+
+    /// Cheap parameters, suitable for running many hashes
+    fn gen_params() -> Self::Params;
+    /// Change the idx-th cost parameter
+    fn mutate_params(params: &Self::Params, idx: usize) -> Self::Params;
+
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8>;
+
+    /// Encoded hash (PHC string, or modular crypt format), None if unsupported
+    fn hash_password_encoded(
+        _password: &[u8],
+        _salt: &[u8],
+        _params: &Self::Params,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Parse an encoded hash and verify the password against it
+    fn verify_encoded(_password: &[u8], _encoded: &str) -> bool {
+        false
+    }
+
+    /// Verify a raw hash through a dedicated API, None if unsupported
+    fn verify_raw(
+        _password: &[u8],
+        _salt: &[u8],
+        _params: &Self::Params,
+        _expected: &[u8],
+    ) -> Option<bool> {
+        None
+    }
+
+    fn gen_input(size: usize) -> PasswordHashInput<Self::Params> {
+        let mut password = vec![0u8; size];
+        let mut salt = vec![0u8; Self::SALTSIZE];
+        campaign_rng().fill_bytes(&mut password);
+        campaign_rng().fill_bytes(&mut salt);
+        (password, salt, Self::gen_params())
+    }
+
+    fn gen_state() {}
+
+    fn call_test_hash(_state: (), input: &PasswordHashInput<Self::Params>) -> Vec<u8> {
+        Self::hash_password(&input.0, &input.1, &input.2)
+    }
+
+    /// Verification of the password, then of the password with its first bit flipped, against
+    /// the encoded hash
+    fn call_test_encoded(
+        _state: (),
+        input: &PasswordHashInput<Self::Params>,
+    ) -> (Option<String>, bool, bool) {
+        let encoded = Self::hash_password_encoded(&input.0, &input.1, &input.2);
+        let mut wrong_password = input.0.clone();
+        flip_one_bit(&mut wrong_password, 0);
+        match &encoded {
+            Some(encoded_hash) => {
+                let verified = Self::verify_encoded(&input.0, encoded_hash);
+                let wrong_verified = Self::verify_encoded(&wrong_password, encoded_hash);
+                (encoded, verified, wrong_verified)
+            }
+            None => (None, false, false),
+        }
+    }
+
+    /// Raw verification of the derived hash, then of the derived hash with its first bit flipped
+    fn call_test_verify_raw(
+        _state: (),
+        input: &PasswordHashInput<Self::Params>,
+    ) -> (Option<bool>, Option<bool>) {
+        let mut derived = Self::hash_password(&input.0, &input.1, &input.2);
+        let verified = Self::verify_raw(&input.0, &input.1, &input.2, &derived);
+        flip_one_bit(&mut derived, 0);
+        let wrong_verified = Self::verify_raw(&input.0, &input.1, &input.2, &derived);
+        (verified, wrong_verified)
+    }
+
+    fn get_password_from_input_as_u8(input: &PasswordHashInput<Self::Params>) -> Vec<u8> {
+        input.0.clone()
+    }
+
+    fn set_password_from_input_as_u8(
+        _state: &(),
+        input: &PasswordHashInput<Self::Params>,
+        password: Vec<u8>,
+    ) -> ((), PasswordHashInput<Self::Params>) {
+        ((), (password, input.1.clone(), input.2.clone()))
+    }
+
+    fn get_salt_from_input_as_u8(input: &PasswordHashInput<Self::Params>) -> Vec<u8> {
+        input.1.clone()
+    }
+
+    fn set_salt_from_input_as_u8(
+        _state: &(),
+        input: &PasswordHashInput<Self::Params>,
+        salt: Vec<u8>,
+    ) -> ((), PasswordHashInput<Self::Params>) {
+        ((), (input.0.clone(), salt, input.2.clone()))
+    }
+
+    /// Runner positions start at 1, position n changes the parameter n - 1
+    fn set_params_from_input(
+        _state: &(),
+        input: &PasswordHashInput<Self::Params>,
+        element_to_mutate: usize,
+    ) -> ((), PasswordHashInput<Self::Params>) {
+        (
+            (),
+            (
+                input.0.clone(),
+                input.1.clone(),
+                Self::mutate_params(&input.2, element_to_mutate - 1),
+            ),
+        )
+    }
+
+    fn bit_inclusion_on_password_relation(
+    ) -> impl MetamorphicRelation<(), PasswordHashInput<Self::Params>, Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on password",
+            BitInclusionMutator::new(
+                Self::get_password_from_input_as_u8,
+                Self::set_password_from_input_as_u8,
+            ),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_password_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_hash)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_password_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_salt_relation(
+    ) -> impl MetamorphicRelation<(), PasswordHashInput<Self::Params>, Vec<u8>> {
+        Relation::new(
+            "Bit Inclusion on salt",
+            BitInclusionMutator::new(
+                Self::get_salt_from_input_as_u8,
+                Self::set_salt_from_input_as_u8,
+            ),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_salt_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_hash)
+            .with_options(options.clone())
+            .run_relation(
+                Self::SALTSIZE * 8,
+                Self::SALTSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_salt_relation(),
+            );
+    }
+
+    /// Changing any cost parameter must change the output
+    fn params_sensitivity_relation(
+    ) -> impl MetamorphicRelation<(), PasswordHashInput<Self::Params>, Vec<u8>> {
+        Relation::new(
+            "Parameters sensitivity",
+            IndexMutator::new(Self::set_params_from_input),
+            outputs_differ,
+        )
+    }
+
+    fn params_sensitivity_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_hash)
+            .with_options(options.clone())
+            // One position per parameter
+            .run_relation(
+                Self::PARAMS_COUNT + 1,
+                Self::PARAMS_COUNT + 1,
+                Self::LIBNAME,
+                &Self::params_sensitivity_relation(),
+            );
+    }
+
+    /// hash -> encode -> parse -> verify must accept the password and reject a wrong one
+    fn encoded_round_trip_relation(
+    ) -> impl MetamorphicRelation<(), PasswordHashInput<Self::Params>, (Option<String>, bool, bool)>
+    {
+        Relation::new(
+            "Encoded hash round trip",
+            BitInclusionMutator::new(
+                Self::get_password_from_input_as_u8,
+                Self::set_password_from_input_as_u8,
+            ),
+            |output: &(Option<String>, bool, bool),
+             _reference_output: &(Option<String>, bool, bool)| output.1 && !output.2,
+        )
+    }
+
+    fn encoded_round_trip_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_encoded)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::encoded_round_trip_relation(),
+            );
+    }
+
+    /// Raw verification must accept the derived hash and reject a modified one
+    fn verify_raw_relation(
+    ) -> impl MetamorphicRelation<(), PasswordHashInput<Self::Params>, (Option<bool>, Option<bool>)>
+    {
+        Relation::new(
+            "Raw verification",
+            BitInclusionMutator::new(
+                Self::get_password_from_input_as_u8,
+                Self::set_password_from_input_as_u8,
+            ),
+            |output: &(Option<bool>, Option<bool>),
+             _reference_output: &(Option<bool>, Option<bool>)| {
+                output.0 == Some(true) && output.1 == Some(false)
+            },
+        )
+    }
+
+    fn verify_raw_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_verify_raw)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::verify_raw_relation());
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        let (password, salt, params) = Self::gen_input(1);
+        Self::bit_inclusion_on_password_test(max_size, options);
+        Self::bit_inclusion_on_salt_test(options);
+        Self::params_sensitivity_test(options);
+        if Self::hash_password_encoded(&password, &salt, &params).is_some() {
+            Self::encoded_round_trip_test(max_size, options);
+        }
+        if Self::verify_raw(&password, &salt, &params, &[]).is_some() {
+            Self::verify_raw_test(max_size, options);
+        }
+    }
+}
+
+/// Key, nonce, associated data and payload (plaintext, or ciphertext with its tag)
+pub type AeadInput = (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>);
+
+/// Sealed outputs of the appended tag and detached APIs, and plaintexts opened by both APIs
+pub type AeadApisOutput = (Vec<u8>, Vec<u8>, Option<Vec<u8>>, Option<Vec<u8>>);
+
+pub trait AeadMetamorphic: 'static {
+    const LIBNAME: &str;
+    const KEYSIZE: usize;
+    const NONCESIZE: usize;
+    const TAGSIZE: usize;
+    /// Size of the associated data used by tests that do not mutate it
+    const AADSIZE: usize = 32;
+
+    /// Encrypt in place, returning the ciphertext with the tag appended
+    fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8>;
+    /// Encrypt with the tag returned separately from the ciphertext
+    fn seal_detached(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> (Vec<u8>, Vec<u8>);
+    /// Decrypt a ciphertext with its appended tag, None if authentication fails
+    fn open(key: &[u8], nonce: &[u8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>>;
+    /// Decrypt a ciphertext with its tag passed separately, None if authentication fails
+    fn open_detached(
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> Option<Vec<u8>>;
+
+    fn gen_input(size: usize) -> AeadInput {
+        let mut key = vec![0u8; Self::KEYSIZE];
+        let mut nonce = vec![0u8; Self::NONCESIZE];
+        let mut aad = vec![0u8; Self::AADSIZE];
+        let mut plaintext = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut key);
+        campaign_rng().fill_bytes(&mut nonce);
+        campaign_rng().fill_bytes(&mut aad);
+        campaign_rng().fill_bytes(&mut plaintext);
+        (key, nonce, aad, plaintext)
+    }
+
+    /// Same as gen_input, with the plaintext replaced by its sealed version
+    fn gen_sealed_input(size: usize) -> AeadInput {
+        let (key, nonce, aad, plaintext) = Self::gen_input(size);
+        let sealed = Self::seal(&key, &nonce, &aad, &plaintext);
+        (key, nonce, aad, sealed)
+    }
+
+    /// Same as gen_input, with an associated data of the given size
+    fn gen_aad_input(size: usize) -> AeadInput {
+        let (key, nonce, _, plaintext) = Self::gen_input(size);
+        let mut aad = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut aad);
+        let sealed = Self::seal(&key, &nonce, &aad, &plaintext);
+        (key, nonce, aad, sealed)
+    }
+
+    fn gen_state() {}
+
+    /// Plaintext and the result of sealing then opening it
+    fn call_test_round_trip(_state: (), input: &AeadInput) -> (Vec<u8>, Option<Vec<u8>>) {
+        let sealed = Self::seal(&input.0, &input.1, &input.2, &input.3);
+        (
+            input.3.clone(),
+            Self::open(&input.0, &input.1, &input.2, &sealed),
+        )
+    }
+
+    fn call_test_open(_state: (), input: &AeadInput) -> Option<Vec<u8>> {
+        Self::open(&input.0, &input.1, &input.2, &input.3)
+    }
+
+    /// Output of the appended tag API, and of the detached API with its tag appended, along with
+    /// the sealed output opened by the appended tag and the detached APIs
+    fn call_test_seal_apis(_state: (), input: &AeadInput) -> AeadApisOutput {
+        let sealed = Self::seal(&input.0, &input.1, &input.2, &input.3);
+        let (ciphertext, tag) = Self::seal_detached(&input.0, &input.1, &input.2, &input.3);
+        let opened = Self::open(&input.0, &input.1, &input.2, &sealed);
+        let opened_detached = Self::open_detached(
+            &input.0,
+            &input.1,
+            &input.2,
+            &sealed[..sealed.len() - Self::TAGSIZE],
+            &sealed[sealed.len() - Self::TAGSIZE..],
+        );
+        (sealed, [ciphertext, tag].concat(), opened, opened_detached)
+    }
+
+    fn get_nonce_from_input_as_u8(input: &AeadInput) -> Vec<u8> {
+        input.1.clone()
+    }
+
+    fn set_nonce_from_input_as_u8(
+        _state: &(),
+        input: &AeadInput,
+        nonce: Vec<u8>,
+    ) -> ((), AeadInput) {
+        (
+            (),
+            (input.0.clone(), nonce, input.2.clone(), input.3.clone()),
+        )
+    }
+
+    fn get_aad_from_input_as_u8(input: &AeadInput) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_aad_from_input_as_u8(_state: &(), input: &AeadInput, aad: Vec<u8>) -> ((), AeadInput) {
+        ((), (input.0.clone(), input.1.clone(), aad, input.3.clone()))
+    }
+
+    fn get_payload_from_input_as_u8(input: &AeadInput) -> Vec<u8> {
+        input.3.clone()
+    }
+
+    fn set_payload_from_input_as_u8(
+        _state: &(),
+        input: &AeadInput,
+        payload: Vec<u8>,
+    ) -> ((), AeadInput) {
+        (
+            (),
+            (input.0.clone(), input.1.clone(), input.2.clone(), payload),
+        )
+    }
+
+    fn get_tag_from_input_as_u8(input: &AeadInput) -> Vec<u8> {
+        input.3[input.3.len() - Self::TAGSIZE..].to_vec()
+    }
+
+    fn set_tag_from_input_as_u8(_state: &(), input: &AeadInput, tag: Vec<u8>) -> ((), AeadInput) {
+        let mut sealed = input.3.clone();
+        let ciphertext_size = sealed.len() - Self::TAGSIZE;
+        sealed[ciphertext_size..].copy_from_slice(&tag);
+        (
+            (),
+            (input.0.clone(), input.1.clone(), input.2.clone(), sealed),
+        )
+    }
+
+    /// open(seal(plaintext)) must give back the plaintext
+    fn round_trip_relation() -> impl MetamorphicRelation<(), AeadInput, (Vec<u8>, Option<Vec<u8>>)>
+    {
+        Relation::new(
+            "Seal/open round trip",
+            BitInclusionMutator::new(
+                Self::get_payload_from_input_as_u8,
+                Self::set_payload_from_input_as_u8,
+            ),
+            |output: &(Vec<u8>, Option<Vec<u8>>),
+             _reference_output: &(Vec<u8>, Option<Vec<u8>>)| {
+                output.1.as_ref() == Some(&output.0)
+            },
+        )
+    }
+
+    fn round_trip_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_round_trip)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::round_trip_relation());
+    }
+
+    fn bit_inclusion_on_ciphertext_relation(
+    ) -> impl MetamorphicRelation<(), AeadInput, Option<Vec<u8>>> {
+        Relation::new(
+            "Bit Inclusion on ciphertext",
+            BitInclusionMutator::new(
+                Self::get_payload_from_input_as_u8,
+                Self::set_payload_from_input_as_u8,
+            ),
+            |output: &Option<Vec<u8>>, _reference_output: &Option<Vec<u8>>| output.is_none(),
+        )
+    }
+
+    fn bit_inclusion_on_ciphertext_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_sealed_input,
+            Self::gen_state,
+            Self::call_test_open,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            1,
+            max_size,
+            Self::LIBNAME,
+            &Self::bit_inclusion_on_ciphertext_relation(),
+        );
+    }
+
+    fn bit_inclusion_on_tag_relation() -> impl MetamorphicRelation<(), AeadInput, Option<Vec<u8>>> {
+        Relation::new(
+            "Bit Inclusion on tag",
+            BitInclusionMutator::new(
+                Self::get_tag_from_input_as_u8,
+                Self::set_tag_from_input_as_u8,
+            ),
+            |output: &Option<Vec<u8>>, _reference_output: &Option<Vec<u8>>| output.is_none(),
+        )
+    }
+
+    fn bit_inclusion_on_tag_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_sealed_input,
+            Self::gen_state,
+            Self::call_test_open,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::TAGSIZE * 8,
+            Self::TAGSIZE * 8,
+            Self::LIBNAME,
+            &Self::bit_inclusion_on_tag_relation(),
+        );
+    }
+
+    fn bit_inclusion_on_nonce_relation() -> impl MetamorphicRelation<(), AeadInput, Option<Vec<u8>>>
+    {
+        Relation::new(
+            "Bit Inclusion on nonce",
+            BitInclusionMutator::new(
+                Self::get_nonce_from_input_as_u8,
+                Self::set_nonce_from_input_as_u8,
+            ),
+            |output: &Option<Vec<u8>>, _reference_output: &Option<Vec<u8>>| output.is_none(),
+        )
+    }
+
+    fn bit_inclusion_on_nonce_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_sealed_input,
+            Self::gen_state,
+            Self::call_test_open,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::NONCESIZE * 8,
+            Self::NONCESIZE * 8,
+            Self::LIBNAME,
+            &Self::bit_inclusion_on_nonce_relation(),
+        );
+    }
+
+    fn bit_inclusion_on_aad_relation() -> impl MetamorphicRelation<(), AeadInput, Option<Vec<u8>>> {
+        Relation::new(
+            "Bit Inclusion on associated data",
+            BitInclusionMutator::new(
+                Self::get_aad_from_input_as_u8,
+                Self::set_aad_from_input_as_u8,
+            ),
+            |output: &Option<Vec<u8>>, _reference_output: &Option<Vec<u8>>| output.is_none(),
+        )
+    }
+
+    fn bit_inclusion_on_aad_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_aad_input, Self::gen_state, Self::call_test_open)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_aad_relation(),
+            );
+    }
+
+    /// Appended and detached tag APIs must agree, both when sealing and when opening
+    fn seal_apis_relation() -> impl MetamorphicRelation<(), AeadInput, AeadApisOutput> {
+        Relation::new(
+            "In place vs detached seal and open",
+            BitInclusionMutator::new(
+                Self::get_payload_from_input_as_u8,
+                Self::set_payload_from_input_as_u8,
+            ),
+            |output: &AeadApisOutput, _reference_output: &AeadApisOutput| {
+                output.0 == output.1 && output.2 == output.3
+            },
+        )
+    }
+
+    fn seal_apis_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_seal_apis)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::seal_apis_relation());
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::round_trip_test(max_size, options);
+        Self::bit_inclusion_on_ciphertext_test(max_size, options);
+        Self::bit_inclusion_on_tag_test(options);
+        Self::bit_inclusion_on_nonce_test(options);
+        Self::bit_inclusion_on_aad_test(max_size, options);
+        Self::seal_apis_test(max_size, options);
+    }
+}
+
+/// Secret key, encoded public key, message and signature
+pub type SignatureInput<K> = (K, Vec<u8>, Vec<u8>, Vec<u8>);
+
+pub trait SignatureMetamorphic: 'static {
+    type SecretKey: std::fmt::Debug + Clone + Send + Sync;
+
+    const LIBNAME: &str;
+    const PKSIZE: usize;
+    const SIGSIZE: usize;
+    /// Signing the same message twice gives the same signature
+    const DETERMINISTIC: bool;
+
+    /// Secret key and encoded public key
+    fn gen_keys() -> (Self::SecretKey, Vec<u8>);
+
+    /// Key generation from a 32 bytes seed, None if unsupported
+    fn gen_keys_from_seed(_seed: &[u8; 32]) -> Option<(Self::SecretKey, Vec<u8>)> {
+        None
+    }
+
+    fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8>;
+    /// Encodings that cannot be parsed are rejected
+    fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool;
+
+    fn gen_input(size: usize) -> SignatureInput<Self::SecretKey> {
+        let (sk, pk) = Self::gen_keys();
+        let mut message = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut message);
+        let signature = Self::sign(&sk, &message);
+        (sk, pk, message, signature)
+    }
+
+    fn gen_state() {}
+
+    /// Sign the message then verify the fresh signature
+    fn call_test_sign_verify(_state: (), input: &SignatureInput<Self::SecretKey>) -> bool {
+        let signature = Self::sign(&input.0, &input.2);
+        Self::verify(&input.1, &input.2, &signature)
+    }
+
+    fn call_test_verify(_state: (), input: &SignatureInput<Self::SecretKey>) -> bool {
+        Self::verify(&input.1, &input.2, &input.3)
+    }
+
+    fn call_test_sign(_state: (), input: &SignatureInput<Self::SecretKey>) -> Vec<u8> {
+        Self::sign(&input.0, &input.2)
+    }
+
+    fn get_pkey_from_input_as_u8(input: &SignatureInput<Self::SecretKey>) -> Vec<u8> {
+        input.1.clone()
+    }
+
+    fn set_pkey_from_input_as_u8(
+        _state: &(),
+        input: &SignatureInput<Self::SecretKey>,
+        pk: Vec<u8>,
+    ) -> ((), SignatureInput<Self::SecretKey>) {
+        ((), (input.0.clone(), pk, input.2.clone(), input.3.clone()))
+    }
+
+    fn get_message_from_input_as_u8(input: &SignatureInput<Self::SecretKey>) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_message_from_input_as_u8(
+        _state: &(),
+        input: &SignatureInput<Self::SecretKey>,
+        message: Vec<u8>,
+    ) -> ((), SignatureInput<Self::SecretKey>) {
+        (
+            (),
+            (input.0.clone(), input.1.clone(), message, input.3.clone()),
+        )
+    }
+
+    fn get_signature_from_input_as_u8(input: &SignatureInput<Self::SecretKey>) -> Vec<u8> {
+        input.3.clone()
+    }
+
+    fn set_signature_from_input_as_u8(
+        _state: &(),
+        input: &SignatureInput<Self::SecretKey>,
+        signature: Vec<u8>,
+    ) -> ((), SignatureInput<Self::SecretKey>) {
+        (
+            (),
+            (input.0.clone(), input.1.clone(), input.2.clone(), signature),
+        )
+    }
+
+    fn sign_verify_relation() -> impl MetamorphicRelation<(), SignatureInput<Self::SecretKey>, bool>
+    {
+        Relation::new(
+            "Sign/verify",
+            BitInclusionMutator::new(
+                Self::get_message_from_input_as_u8,
+                Self::set_message_from_input_as_u8,
+            ),
+            |output: &bool, _reference_output: &bool| *output,
+        )
+    }
+
+    fn sign_verify_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input,
+            Self::gen_state,
+            Self::call_test_sign_verify,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::sign_verify_relation());
+    }
+
+    /// A signature must not verify once the message has changed
+    fn bit_inclusion_on_message_relation(
+    ) -> impl MetamorphicRelation<(), SignatureInput<Self::SecretKey>, bool> {
+        Relation::new(
+            "Bit Inclusion on message",
+            BitInclusionMutator::new(
+                Self::get_message_from_input_as_u8,
+                Self::set_message_from_input_as_u8,
+            ),
+            |output: &bool, _reference_output: &bool| !*output,
+        )
+    }
+
+    fn bit_inclusion_on_message_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_verify)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_message_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_signature_relation(
+    ) -> impl MetamorphicRelation<(), SignatureInput<Self::SecretKey>, bool> {
+        Relation::new(
+            "Bit Inclusion on signature",
+            BitInclusionMutator::new(
+                Self::get_signature_from_input_as_u8,
+                Self::set_signature_from_input_as_u8,
+            ),
+            |output: &bool, _reference_output: &bool| !*output,
+        )
+    }
+
+    fn bit_inclusion_on_signature_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_verify)
+            .with_options(options.clone())
+            .run_relation(
+                Self::SIGSIZE * 8,
+                Self::SIGSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_signature_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_pkey_relation(
+    ) -> impl MetamorphicRelation<(), SignatureInput<Self::SecretKey>, bool> {
+        Relation::new(
+            "Bit Inclusion on public key",
+            BitInclusionMutator::new(
+                Self::get_pkey_from_input_as_u8,
+                Self::set_pkey_from_input_as_u8,
+            ),
+            |output: &bool, _reference_output: &bool| !*output,
+        )
+    }
+
+    fn bit_inclusion_on_pkey_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_verify)
+            .with_options(options.clone())
+            .run_relation(
+                Self::PKSIZE * 8,
+                Self::PKSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_pkey_relation(),
+            );
+    }
+
+    /// Deterministic schemes must give the same signature twice, randomized ones must not
+    fn signing_determinism_relation(
+    ) -> impl MetamorphicRelation<(), SignatureInput<Self::SecretKey>, Vec<u8>> {
+        Relation::new(
+            "Signing determinism",
+            IdentityMutator,
+            |output: &Vec<u8>, reference_output: &Vec<u8>| {
+                (output == reference_output) == Self::DETERMINISTIC
+            },
+        )
+    }
+
+    fn signing_determinism_test(iterations: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_sign)
+            .with_options(options.clone())
+            .run_relation(
+                iterations,
+                iterations,
+                Self::LIBNAME,
+                &Self::signing_determinism_relation(),
+            );
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::sign_verify_test(max_size, options);
+        Self::bit_inclusion_on_message_test(max_size, options);
+        Self::bit_inclusion_on_signature_test(options);
+        Self::bit_inclusion_on_pkey_test(options);
+        Self::signing_determinism_test(max_size, options);
+    }
+}
+
+/// Own private key, peer private key and peer public key
+pub type KeyAgreementInput = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+pub trait KeyAgreementMetamorphic: 'static {
+    const LIBNAME: &str;
+    const PRIVATESIZE: usize;
+    const PUBLICSIZE: usize;
+
+    fn gen_private() -> Vec<u8> {
+        let mut private_key = vec![0u8; Self::PRIVATESIZE];
+        campaign_rng().fill_bytes(&mut private_key);
+        private_key
+    }
+
+    fn public_key(private_key: &[u8]) -> Vec<u8>;
+    /// Shared secret, None if the peer public key is rejected
+    fn agree(private_key: &[u8], peer_public_key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Public keys that must be rejected (low order, not on the curve, ...)
+    fn invalid_public_keys() -> Vec<Vec<u8>> {
+        vec![]
+    }
+
+    /// Drop the public key bits that the scheme ignores, e.g. the X25519 top bit
+    fn normalize_public_key(public_key: &[u8]) -> Vec<u8> {
+        public_key.to_vec()
+    }
+
+    fn gen_input(_size: usize) -> KeyAgreementInput {
+        let private_key = Self::gen_private();
+        let peer_private_key = Self::gen_private();
+        let peer_public_key = Self::public_key(&peer_private_key);
+        (private_key, peer_private_key, peer_public_key)
+    }
+
+    fn gen_state() {}
+
+    /// Shared secrets computed on both sides
+    fn call_test_commutativity(
+        _state: (),
+        input: &KeyAgreementInput,
+    ) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+        (
+            Self::agree(&input.0, &Self::public_key(&input.1)),
+            Self::agree(&input.1, &Self::public_key(&input.0)),
+        )
+    }
+
+    /// Shared secret with the peer public key, and whether that key is equivalent to the one
+    /// derived from the peer private key
+    fn call_test_peer_public(_state: (), input: &KeyAgreementInput) -> (Option<Vec<u8>>, bool) {
+        let peer_public_key = Self::public_key(&input.1);
+        (
+            Self::agree(&input.0, &input.2),
+            Self::normalize_public_key(&input.2) == Self::normalize_public_key(&peer_public_key),
+        )
+    }
+
+    fn get_private_from_input_as_u8(input: &KeyAgreementInput) -> Vec<u8> {
+        input.0.clone()
+    }
+
+    fn set_private_from_input_as_u8(
+        _state: &(),
+        input: &KeyAgreementInput,
+        private_key: Vec<u8>,
+    ) -> ((), KeyAgreementInput) {
+        ((), (private_key, input.1.clone(), input.2.clone()))
+    }
+
+    fn get_peer_public_from_input_as_u8(input: &KeyAgreementInput) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_peer_public_from_input_as_u8(
+        _state: &(),
+        input: &KeyAgreementInput,
+        peer_public_key: Vec<u8>,
+    ) -> ((), KeyAgreementInput) {
+        ((), (input.0.clone(), input.1.clone(), peer_public_key))
+    }
+
+    /// Runner positions start at 1, position n uses the invalid public key n - 1
+    fn set_invalid_peer_public(
+        _state: &(),
+        input: &KeyAgreementInput,
+        element_to_mutate: usize,
+    ) -> ((), KeyAgreementInput) {
+        let invalid_public_keys = Self::invalid_public_keys();
+        (
+            (),
+            (
+                input.0.clone(),
+                input.1.clone(),
+                invalid_public_keys[element_to_mutate - 1].clone(),
+            ),
+        )
+    }
+
+    /// agree(a, B) == agree(b, A), for every private key a one bit away from the initial one
+    fn commutativity_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, Option<Vec<u8>>)> {
+        Relation::new(
+            "Commutativity",
+            BitInclusionMutator::new(
+                Self::get_private_from_input_as_u8,
+                Self::set_private_from_input_as_u8,
+            ),
+            |output: &(Option<Vec<u8>>, Option<Vec<u8>>),
+             _reference_output: &(Option<Vec<u8>>, Option<Vec<u8>>)| {
+                output.0.is_some() && output.0 == output.1
+            },
+        )
+    }
+
+    fn commutativity_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input,
+            Self::gen_state,
+            Self::call_test_commutativity,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::PRIVATESIZE * 8,
+            Self::PRIVATESIZE * 8,
+            Self::LIBNAME,
+            &Self::commutativity_relation(),
+        );
+    }
+
+    /// A modified peer public key must be rejected or give another shared secret
+    fn bit_inclusion_on_peer_public_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, bool)> {
+        Relation::new(
+            "Bit Inclusion on peer public key",
+            BitInclusionMutator::new(
+                Self::get_peer_public_from_input_as_u8,
+                Self::set_peer_public_from_input_as_u8,
+            ),
+            |output: &(Option<Vec<u8>>, bool), reference_output: &(Option<Vec<u8>>, bool)| {
+                (output.0 == reference_output.0) == output.1
+            },
+        )
+    }
+
+    fn bit_inclusion_on_peer_public_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input,
+            Self::gen_state,
+            Self::call_test_peer_public,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::PUBLICSIZE * 8,
+            Self::PUBLICSIZE * 8,
+            Self::LIBNAME,
+            &Self::bit_inclusion_on_peer_public_relation(),
+        );
+    }
+
+    fn invalid_public_key_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, bool)> {
+        Relation::new(
+            "Invalid public key rejection",
+            IndexMutator::new(Self::set_invalid_peer_public),
+            |output: &(Option<Vec<u8>>, bool), _reference_output: &(Option<Vec<u8>>, bool)| {
+                output.0.is_none()
+            },
+        )
+    }
+
+    fn invalid_public_key_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input,
+            Self::gen_state,
+            Self::call_test_peer_public,
+        )
+        .with_options(options.clone())
+        // One position per invalid public key
+        .run_relation(
+            Self::invalid_public_keys().len() + 1,
+            Self::invalid_public_keys().len() + 1,
+            Self::LIBNAME,
+            &Self::invalid_public_key_relation(),
+        );
+    }
+
+    fn run_tests() {
+        Self::run_tests_with_options(&CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(options: &CampaignOptions) {
+        Self::commutativity_test(options);
+        Self::bit_inclusion_on_peer_public_test(options);
+        if !Self::invalid_public_keys().is_empty() {
+            Self::invalid_public_key_test(options);
+        }
+    }
+}
+
+/// Key agreement whose private keys can only be generated by the implementation (e.g. ring), it
+/// is checked against a reference implementation of the same scheme that can import private keys
+pub trait GeneratedKeyAgreementMetamorphic: 'static {
+    type PrivateKey;
+    type Reference: KeyAgreementMetamorphic;
+
+    const LIBNAME: &str;
+
+    /// Fresh private key along with its public key
+    fn generate() -> (Self::PrivateKey, Vec<u8>);
+    /// Shared secret, None if the peer public key is rejected
+    fn agree(private_key: Self::PrivateKey, peer_public_key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Shared secret of a generated key with the peer public key, shared secret of the reference
+    /// peer private key with the generated public key, and whether the peer public key is
+    /// equivalent to the one derived from the peer private key
+    fn call_test_reference(
+        _state: (),
+        input: &KeyAgreementInput,
+    ) -> (Option<Vec<u8>>, Option<Vec<u8>>, bool) {
+        let (private_key, public_key) = Self::generate();
+        let peer_public_key = Self::Reference::public_key(&input.1);
+        (
+            Self::agree(private_key, &input.2),
+            Self::Reference::agree(&input.1, &public_key),
+            Self::Reference::normalize_public_key(&input.2)
+                == Self::Reference::normalize_public_key(&peer_public_key),
+        )
+    }
+
+    /// Change the reference peer private key, along with its public key
+    fn set_peer_private_from_input_as_u8(
+        _state: &(),
+        input: &KeyAgreementInput,
+        peer_private_key: Vec<u8>,
+    ) -> ((), KeyAgreementInput) {
+        let peer_public_key = Self::Reference::public_key(&peer_private_key);
+        ((), (input.0.clone(), peer_private_key, peer_public_key))
+    }
+
+    fn get_peer_private_from_input_as_u8(input: &KeyAgreementInput) -> Vec<u8> {
+        input.1.clone()
+    }
+
+    /// Both sides must agree on the shared secret, for every reference private key one bit away
+    /// from the initial one
+    fn reference_agreement_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, Option<Vec<u8>>, bool)>
+    {
+        Relation::new(
+            "Reference key agreement",
+            BitInclusionMutator::new(
+                Self::get_peer_private_from_input_as_u8,
+                Self::set_peer_private_from_input_as_u8,
+            ),
+            |output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool),
+             _reference_output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool)| {
+                output.0.is_some() && output.0 == output.1
+            },
+        )
+    }
+
+    fn reference_agreement_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::Reference::gen_input, || (), Self::call_test_reference)
+            .with_options(options.clone())
+            .run_relation(
+                Self::Reference::PRIVATESIZE * 8,
+                Self::Reference::PRIVATESIZE * 8,
+                &format!("{} vs {}", Self::LIBNAME, Self::Reference::LIBNAME),
+                &Self::reference_agreement_relation(),
+            );
+    }
+
+    /// A modified peer public key must be rejected or give another shared secret than the one
+    /// computed by the reference
+    fn bit_inclusion_on_peer_public_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, Option<Vec<u8>>, bool)>
+    {
+        Relation::new(
+            "Bit Inclusion on peer public key",
+            BitInclusionMutator::new(
+                Self::Reference::get_peer_public_from_input_as_u8,
+                Self::Reference::set_peer_public_from_input_as_u8,
+            ),
+            |output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool),
+             _reference_output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool)| {
+                (output.0 == output.1) == output.2
+            },
+        )
+    }
+
+    fn bit_inclusion_on_peer_public_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::Reference::gen_input, || (), Self::call_test_reference)
+            .with_options(options.clone())
+            .run_relation(
+                Self::Reference::PUBLICSIZE * 8,
+                Self::Reference::PUBLICSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_peer_public_relation(),
+            );
+    }
+
+    fn invalid_public_key_relation(
+    ) -> impl MetamorphicRelation<(), KeyAgreementInput, (Option<Vec<u8>>, Option<Vec<u8>>, bool)>
+    {
+        Relation::new(
+            "Invalid public key rejection",
+            IndexMutator::new(Self::Reference::set_invalid_peer_public),
+            |output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool),
+             _reference_output: &(Option<Vec<u8>>, Option<Vec<u8>>, bool)| {
+                output.0.is_none()
+            },
+        )
+    }
+
+    fn invalid_public_key_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::Reference::gen_input, || (), Self::call_test_reference)
+            .with_options(options.clone())
+            // One position per invalid public key
+            .run_relation(
+                Self::Reference::invalid_public_keys().len() + 1,
+                Self::Reference::invalid_public_keys().len() + 1,
+                Self::LIBNAME,
+                &Self::invalid_public_key_relation(),
+            );
+    }
+
+    fn run_tests() {
+        Self::run_tests_with_options(&CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(options: &CampaignOptions) {
+        Self::reference_agreement_test(options);
+        Self::bit_inclusion_on_peer_public_test(options);
+        if !Self::Reference::invalid_public_keys().is_empty() {
+            Self::invalid_public_key_test(options);
+        }
+    }
+}
+
+/// Key, IV, plaintext, and a byte position used as split point or keystream offset
+pub type CipherInput = (Vec<u8>, Vec<u8>, Vec<u8>, usize);
+
+pub trait CipherMetamorphic: 'static {
+    /// Keyed encryption context, keeping the chaining value or keystream position across calls
+    type Encryptor;
+
+    const LIBNAME: &str;
+    const KEYSIZE: usize;
+    /// 0 when the mode has no IV
+    const IVSIZE: usize;
+    /// 1 for stream ciphers, plaintexts are a multiple of it
+    const BLOCKSIZE: usize;
+    /// The ciphertext is the plaintext XORed with a keystream (CTR, stream ciphers)
+    const STREAM: bool;
+
+    fn new_encryptor(key: &[u8], iv: &[u8]) -> Self::Encryptor;
+    /// Encrypt in place, `data` is a multiple of BLOCKSIZE
+    fn encrypt_update(encryptor: &mut Self::Encryptor, data: &mut [u8]);
+    fn decrypt(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8>;
+
+    /// Move the keystream to the given byte offset, false if unsupported
+    fn seek(_encryptor: &mut Self::Encryptor, _offset: usize) -> bool {
+        false
+    }
+
+    fn encrypt(key: &[u8], iv: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut encryptor = Self::new_encryptor(key, iv);
+        let mut buffer = plaintext.to_vec();
+        Self::encrypt_update(&mut encryptor, &mut buffer);
+        buffer
+    }
+
+    /// Random key and IV, random plaintext of `size` blocks
+    fn gen_input(size: usize) -> CipherInput {
+        let mut key = vec![0u8; Self::KEYSIZE];
+        let mut iv = vec![0u8; Self::IVSIZE];
+        let mut plaintext = vec![0u8; size * Self::BLOCKSIZE];
+        campaign_rng().fill_bytes(&mut key);
+        campaign_rng().fill_bytes(&mut iv);
+        campaign_rng().fill_bytes(&mut plaintext);
+        (key, iv, plaintext, 0)
+    }
+
+    fn gen_state() {}
+
+    /// Plaintext and the result of encrypting then decrypting it
+    fn call_test_round_trip(_state: (), input: &CipherInput) -> (Vec<u8>, Vec<u8>) {
+        let ciphertext = Self::encrypt(&input.0, &input.1, &input.2);
+        (
+            input.2.clone(),
+            Self::decrypt(&input.0, &input.1, &ciphertext),
+        )
+    }
+
+    /// Encryption in one call, and in two calls split at the input position
+    fn call_test_split(_state: (), input: &CipherInput) -> (Vec<u8>, Vec<u8>) {
+        let mut encryptor = Self::new_encryptor(&input.0, &input.1);
+        let mut buffer = input.2.clone();
+        let (first, second) = buffer.split_at_mut(input.3);
+        Self::encrypt_update(&mut encryptor, first);
+        Self::encrypt_update(&mut encryptor, second);
+        (Self::encrypt(&input.0, &input.1, &input.2), buffer)
+    }
+
+    /// Encryption after seeking to the input position, and encryption after discarding as many
+    /// keystream bytes, None if seeking is unsupported
+    fn call_test_seek(_state: (), input: &CipherInput) -> Option<(Vec<u8>, Vec<u8>)> {
+        let mut encryptor = Self::new_encryptor(&input.0, &input.1);
+        let mut buffer = input.2.clone();
+        if !Self::seek(&mut encryptor, input.3) {
+            return None;
+        }
+        Self::encrypt_update(&mut encryptor, &mut buffer);
+        let discarded = [vec![0u8; input.3], input.2.clone()].concat();
+        let ciphertext = Self::encrypt(&input.0, &input.1, &discarded);
+        Some((buffer, ciphertext[input.3..].to_vec()))
+    }
+
+    /// Plaintext and ciphertext
+    fn call_test_encrypt(_state: (), input: &CipherInput) -> (Vec<u8>, Vec<u8>) {
+        (input.2.clone(), Self::encrypt(&input.0, &input.1, &input.2))
+    }
+
+    fn get_plaintext_from_input_as_u8(input: &CipherInput) -> Vec<u8> {
+        input.2.clone()
+    }
+
+    fn set_plaintext_from_input_as_u8(
+        _state: &(),
+        input: &CipherInput,
+        plaintext: Vec<u8>,
+    ) -> ((), CipherInput) {
+        ((), (input.0.clone(), input.1.clone(), plaintext, input.3))
+    }
+
+    /// The position is a whole number of blocks
+    fn set_position_from_input(
+        _state: &(),
+        input: &CipherInput,
+        element_to_mutate: usize,
+    ) -> ((), CipherInput) {
+        (
+            (),
+            (
+                input.0.clone(),
+                input.1.clone(),
+                input.2.clone(),
+                element_to_mutate * Self::BLOCKSIZE,
+            ),
+        )
+    }
+
+    /// decrypt(encrypt(m)) == m
+    fn round_trip_relation() -> impl MetamorphicRelation<(), CipherInput, (Vec<u8>, Vec<u8>)> {
+        Relation::new(
+            "Encrypt/decrypt round trip",
+            BitInclusionMutator::new(
+                Self::get_plaintext_from_input_as_u8,
+                Self::set_plaintext_from_input_as_u8,
+            ),
+            |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| {
+                output.0 == output.1
+            },
+        )
+    }
+
+    fn round_trip_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_round_trip)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::round_trip_relation());
+    }
+
+    fn split_encryption_relation() -> impl MetamorphicRelation<(), CipherInput, (Vec<u8>, Vec<u8>)>
+    {
+        Relation::new(
+            "Split encryption",
+            IndexMutator::new(Self::set_position_from_input),
+            |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| {
+                output.0 == output.1
+            },
+        )
+    }
+
+    fn split_encryption_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_split)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::split_encryption_relation(),
+            );
+    }
+
+    /// Seeking to offset n equals generating n keystream bytes and discarding them
+    fn seek_relation() -> impl MetamorphicRelation<(), CipherInput, Option<(Vec<u8>, Vec<u8>)>> {
+        Relation::new(
+            "Keystream seek",
+            IndexMutator::new(Self::set_position_from_input),
+            |output: &Option<(Vec<u8>, Vec<u8>)>,
+             _reference_output: &Option<(Vec<u8>, Vec<u8>)>| {
+                output
+                    .as_ref()
+                    .is_some_and(|(seeked, discarded)| seeked == discarded)
+            },
+        )
+    }
+
+    fn seek_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_seek)
+            .with_options(options.clone())
+            .run_relation(1, max_size, Self::LIBNAME, &Self::seek_relation());
+    }
+
+    /// In a keystream mode, a plaintext bit flip flips exactly the same ciphertext bit
+    fn bit_flip_locality_relation() -> impl MetamorphicRelation<(), CipherInput, (Vec<u8>, Vec<u8>)>
+    {
+        Relation::new(
+            "Bit flip locality",
+            BitInclusionMutator::new(
+                Self::get_plaintext_from_input_as_u8,
+                Self::set_plaintext_from_input_as_u8,
+            ),
+            |output: &(Vec<u8>, Vec<u8>), reference_output: &(Vec<u8>, Vec<u8>)| {
+                let plaintext_diff: Vec<u8> = output
+                    .0
+                    .iter()
+                    .zip(&reference_output.0)
+                    .map(|(a, b)| a ^ b)
+                    .collect();
+                let ciphertext_diff: Vec<u8> = output
+                    .1
+                    .iter()
+                    .zip(&reference_output.1)
+                    .map(|(a, b)| a ^ b)
+                    .collect();
+                let flipped_bits: u32 = ciphertext_diff.iter().map(|b| b.count_ones()).sum();
+                flipped_bits == 1 && plaintext_diff == ciphertext_diff
+            },
+        )
+    }
+
+    fn bit_flip_locality_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, Self::gen_state, Self::call_test_encrypt)
+            .with_options(options.clone())
+            .run_relation(
+                1,
+                max_size,
+                Self::LIBNAME,
+                &Self::bit_flip_locality_relation(),
+            );
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::round_trip_test(max_size, options);
+        Self::split_encryption_test(max_size, options);
+        if Self::STREAM {
+            Self::seek_test(max_size, options);
+            Self::bit_flip_locality_test(max_size, options);
+        }
+    }
+}
+
+/// Output size used to check that an input bit flip affects every output position
+pub const XOF_DIFFUSION_OUTPUT_SIZE: usize = 256;
+/// Window size in which at least one output byte must change
+pub const XOF_DIFFUSION_WINDOW_SIZE: usize = 16;
+/// Output size of the reference read in the prefix and split read relations, spans several
+/// blocks of every sponge rate
+pub const XOF_READ_OUTPUT_SIZE: usize = 1024;
+/// Largest number of read calls the reference read is split into
+pub const XOF_MAX_SPLIT_READS: usize = 8;
+
+pub trait XofMetamorphic: 'static {
+    type Input: std::fmt::Debug + Clone + Send + Sync;
+    type State: Clone + Send + Sync;
+
+    const LIBNAME: &str;
+
+    fn initial_state() -> Self::State;
+    fn gen_input(size: usize) -> Self::Input;
+    fn input_as_u8(input: &Self::Input) -> Vec<u8>;
+
+    /// Return unmodified initial state and mutated as input
+    fn u8_as_input(
+        initial_state: &Self::State,
+        _initial_input: &Self::Input,
+        mutated: Vec<u8>,
+    ) -> (Self::State, Self::Input);
+
+    /// Absorb the input, then squeeze one read per entry of `reads` and concatenate them
+    fn xof(state: Self::State, input: &Self::Input, reads: &[usize]) -> Vec<u8>;
+
+    /// Input along with a single read of `XOF_READ_OUTPUT_SIZE` bytes
+    fn gen_input_read_test(size: usize) -> (Self::Input, Vec<usize>) {
+        (Self::gen_input(size), vec![XOF_READ_OUTPUT_SIZE])
+    }
+
+    fn gen_input_diffusion_test(size: usize) -> (Self::Input, Vec<usize>) {
+        (Self::gen_input(size), vec![XOF_DIFFUSION_OUTPUT_SIZE])
+    }
+
+    fn call_test(state: Self::State, input: &(Self::Input, Vec<usize>)) -> Vec<u8> {
+        Self::xof(state, &input.0, &input.1)
+    }
+
+    /// Single read of a shorter length
+    fn set_prefix_read_from_input(
+        initial_state: &Self::State,
+        input: &(Self::Input, Vec<usize>),
+        rng: &mut StdRng,
+    ) -> (Self::State, (Self::Input, Vec<usize>)) {
+        let total: usize = input.1.iter().sum();
+        (
+            initial_state.clone(),
+            (input.0.clone(), vec![rng.gen_range(0..total)]),
+        )
+    }
+
+    /// Same total length read in up to `XOF_MAX_SPLIT_READS` calls at random split points
+    fn set_split_reads_from_input(
+        initial_state: &Self::State,
+        input: &(Self::Input, Vec<usize>),
+        rng: &mut StdRng,
+    ) -> (Self::State, (Self::Input, Vec<usize>)) {
+        let total: usize = input.1.iter().sum();
+        let mut splits: Vec<usize> = (1..rng.gen_range(2..=XOF_MAX_SPLIT_READS))
+            .map(|_| rng.gen_range(0..=total))
+            .chain([0, total])
+            .collect();
+        splits.sort_unstable();
+        let reads = splits.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        (initial_state.clone(), (input.0.clone(), reads))
+    }
+
+    fn get_message_from_input_as_u8(input: &(Self::Input, Vec<usize>)) -> Vec<u8> {
+        Self::input_as_u8(&input.0)
+    }
+
+    fn set_message_from_input_as_u8(
+        initial_state: &Self::State,
+        input: &(Self::Input, Vec<usize>),
+        mutated: Vec<u8>,
+    ) -> (Self::State, (Self::Input, Vec<usize>)) {
+        let (state, message) = Self::u8_as_input(initial_state, &input.0, mutated);
+        (state, (message, input.1.clone()))
+    }
+
+    /// Reading n bytes must give a prefix of reading m > n bytes
+    fn prefix_relation() -> impl MetamorphicRelation<Self::State, (Self::Input, Vec<usize>), Vec<u8>>
+    {
+        Relation::new(
+            "XOF Prefix",
+            RandomMutator::new(Self::set_prefix_read_from_input),
+            |output: &Vec<u8>, reference_output: &Vec<u8>| reference_output.starts_with(output),
+        )
+    }
+
+    fn prefix_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input_read_test,
+            Self::initial_state,
+            Self::call_test,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::prefix_relation());
+    }
+
+    /// Reading in several calls must give the same bytes as reading in one call
+    fn split_read_relation(
+    ) -> impl MetamorphicRelation<Self::State, (Self::Input, Vec<usize>), Vec<u8>> {
+        Relation::new(
+            "XOF Split Read",
+            RandomMutator::new(Self::set_split_reads_from_input),
+            |output: &Vec<u8>, reference_output: &Vec<u8>| reference_output == output,
+        )
+    }
+
+    fn split_read_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input_read_test,
+            Self::initial_state,
+            Self::call_test,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::split_read_relation());
+    }
+
+    /// Flipping an input bit must change every window of the output
+    fn bit_inclusion_relation(
+    ) -> impl MetamorphicRelation<Self::State, (Self::Input, Vec<usize>), Vec<u8>> {
+        Relation::new(
+            "XOF Bit Inclusion",
+            BitInclusionMutator::new(
+                Self::get_message_from_input_as_u8,
+                Self::set_message_from_input_as_u8,
+            ),
+            |output: &Vec<u8>, reference_output: &Vec<u8>| {
+                output
+                    .chunks(XOF_DIFFUSION_WINDOW_SIZE)
+                    .zip(reference_output.chunks(XOF_DIFFUSION_WINDOW_SIZE))
+                    .all(|(window, reference_window)| window != reference_window)
+            },
+        )
+    }
+
+    fn bit_inclusion_test(max_size: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input_diffusion_test,
+            Self::initial_state,
+            Self::call_test,
+        )
+        .with_options(options.clone())
+        .run_relation(1, max_size, Self::LIBNAME, &Self::bit_inclusion_relation());
+    }
+
+    fn run_tests(max_size: usize) {
+        Self::run_tests_with_options(max_size, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(max_size: usize, options: &CampaignOptions) {
+        Self::prefix_test(max_size, options);
+        Self::split_read_test(max_size, options);
+        Self::bit_inclusion_test(max_size, options);
+    }
+}
+
+/// Secret key, public key and ciphertext
+pub type KemInput<T> = (
+    <T as KEMMetamorphic>::SecretKey,
+    <T as KEMMetamorphic>::PublicKey,
+    <T as KEMMetamorphic>::CipherText,
+);
+
+pub trait KEMMetamorphic: 'static {
+    type SecretKey: std::fmt::Debug + Clone + Send + Sync;
+    type PublicKey: std::fmt::Debug + Clone + Send + Sync;
+    type CipherText: std::fmt::Debug + Clone + Send + Sync;
+    type SharedSecret: std::fmt::Debug + Clone + Send + Sync + PartialEq;
+    type State: Clone + Send + Sync;
+
+    const LIBNAME: &str;
+    const PKSIZE: usize;
+    const SKSIZE: usize;
+    const CTSIZE: usize;
+    const SSSIZE: usize;
+    /// Whether `public_from_secret` is implemented
+    const SUPPORTS_PUBLIC_FROM_SECRET: bool = false;
+    /// Specification implemented, derandomized transcripts only match within one specification
+    const SPECIFICATION: &str = "FIPS 203";
+
+    fn gen_keys() -> (Self::SecretKey, Self::PublicKey);
+
+    fn decaps(sk: &Self::SecretKey, ct: &Self::CipherText) -> Self::SharedSecret;
+    fn encaps(pk: &Self::PublicKey) -> (Self::SharedSecret, Self::CipherText);
+
+    /// Derandomized key generation from the seeds `d` and `z`, None if unsupported
+    fn gen_keys_from_seed(
+        _d: &[u8; 32],
+        _z: &[u8; 32],
+    ) -> Option<(Self::SecretKey, Self::PublicKey)> {
+        None
+    }
+
+    /// Derandomized encapsulation of the message `m`, None if unsupported
+    fn encaps_from_seed(
+        _pk: &Self::PublicKey,
+        _m: &[u8; 32],
+    ) -> Option<(Self::SharedSecret, Self::CipherText)> {
+        None
+    }
+
+    /// Extract the public key embedded in a secret key, None if unsupported
+    fn public_from_secret(_sk: &Self::SecretKey) -> Option<Self::PublicKey> {
+        None
+    }
+
+    fn gen_input_sk_test(_msg_size: usize) -> (Self::SecretKey, Self::PublicKey, Self::CipherText) {
+        let (sk, pk) = Self::gen_keys();
+        let res = Self::encaps(&pk);
+        (sk, pk, res.1)
+    }
+
+    fn gen_input_pk_test(_msg_size: usize) -> (Self::SecretKey, Self::PublicKey) {
+        Self::gen_keys()
+    }
+
+    fn gen_state() -> Self::State;
+
+    fn get_skey_from_input_as_u8(
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Vec<u8>;
+
+    fn get_pkey_from_input_as_u8(
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Vec<u8>;
+
+    fn set_skey_from_input_as_u8(
+        _state: &Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+        key: Vec<u8>,
+    ) -> (Self::State, KemInput<Self>);
+
+    /// Byte view of the secret key, flipped in place by the secret key bit inclusion test. When
+    /// `None`, the key goes through `get_skey_from_input_as_u8` and `set_skey_from_input_as_u8`
+    fn skey_bytes_mut(
+        _input: &mut (Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Option<&mut [u8]> {
+        None
+    }
+
+    fn set_pkey_from_input_as_u8(
+        _state: &Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+        key: Vec<u8>,
+    ) -> (Self::State, KemInput<Self>);
+
+    /// Byte encoding of the ciphertext, None if unsupported
+    fn get_ciphertext_from_input_as_u8(
+        _input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn output_as_u8(output: Self::SharedSecret) -> Vec<u8>;
+
+    /// Offset of the public key embedded in the secret key, Kyber layout `dk_pke || pk || H(pk) || z`
+    fn embedded_pkey_offset() -> usize {
+        Self::SKSIZE - Self::PKSIZE - 64
+    }
+
+    fn get_embedded_pkey_from_input_as_u8(
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Vec<u8> {
+        let offset = Self::embedded_pkey_offset();
+        Self::get_skey_from_input_as_u8(input)[offset..offset + Self::PKSIZE].to_vec()
+    }
+
+    fn set_embedded_pkey_from_input_as_u8(
+        state: &Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+        key: Vec<u8>,
+    ) -> (Self::State, KemInput<Self>) {
+        let offset = Self::embedded_pkey_offset();
+        let mut sk = Self::get_skey_from_input_as_u8(input);
+        sk[offset..offset + Self::PKSIZE].copy_from_slice(&key);
+        Self::set_skey_from_input_as_u8(state, input, sk)
+    }
+
+    /// Run keygen, encaps and decaps from a `d || z || m` seed, None if derandomization is unsupported
+    fn derandomized_transcript(seed: &[u8; KEM_SEED_SIZE]) -> Option<KEMTranscript> {
+        let d = seed[..32].try_into().unwrap();
+        let z = seed[32..64].try_into().unwrap();
+        let m = seed[64..].try_into().unwrap();
+        let (sk, pk) = Self::gen_keys_from_seed(d, z)?;
+        let (ss, ct) = Self::encaps_from_seed(&pk, m)?;
+        let decapsulated = Self::decaps(&sk, &ct);
+        let input = (sk, pk, ct);
+        Some(KEMTranscript {
+            secret_key: Self::get_skey_from_input_as_u8(&input),
+            public_key: Self::get_pkey_from_input_as_u8(&input),
+            ciphertext: Self::get_ciphertext_from_input_as_u8(&input)?,
+            shared_secret: Self::output_as_u8(ss),
+            decapsulated: Self::output_as_u8(decapsulated),
+        })
+    }
+
+    fn call_test_sk(
+        _state: Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Self::SharedSecret {
+        Self::decaps(&input.0, &input.2)
+    }
+
+    /// Fallible encapsulation, None when the public key is rejected
+    fn try_encaps(pk: &Self::PublicKey) -> Option<(Self::SharedSecret, Self::CipherText)> {
+        Some(Self::encaps(pk))
+    }
+
+    fn call_test_encaps(
+        _state: Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> Option<(Self::SharedSecret, Self::CipherText)> {
+        Self::try_encaps(&input.1)
+    }
+
+    /// Whether the public key derived from the secret key matches the generated one, along with
+    /// the decapsulated shared secret
+    fn call_test_embedded_pk(
+        _state: Self::State,
+        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
+    ) -> (Option<bool>, Self::SharedSecret) {
+        (
+            Self::public_from_secret(&input.0).map(|pk| {
+                let derived = (input.0.clone(), pk, input.2.clone());
+                Self::get_pkey_from_input_as_u8(&derived) == Self::get_pkey_from_input_as_u8(input)
+            }),
+            Self::decaps(&input.0, &input.2),
+        )
+    }
+
+    fn bit_inclusion_on_skey_relation() -> impl MetamorphicRelation<
+        Self::State,
+        (Self::SecretKey, Self::PublicKey, Self::CipherText),
+        Self::SharedSecret,
+    > {
+        Relation::new(
+            "Bit Inclusion on secret key",
+            BitInclusionMutator::new(
+                Self::get_skey_from_input_as_u8,
+                Self::set_skey_from_input_as_u8,
+            )
+            .with_bytes_mut(Self::skey_bytes_mut),
+            outputs_differ,
+        )
+    }
+
+    fn bit_inclusion_on_skey_test() {
+        Self::bit_inclusion_on_skey_test_with_options(&CampaignOptions::default());
+    }
+
+    fn bit_inclusion_on_skey_test_with_options(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input_sk_test, Self::gen_state, Self::call_test_sk)
+            .with_options(options.clone())
+            .run_relation(
+                Self::SKSIZE * 8,
+                Self::SKSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_skey_relation(),
+            );
+    }
+
+    /// FIPS 203 encapsulation key check: public keys holding a coefficient that is not reduced
+    /// mod q must be rejected. Assumes the Kyber encoding (12-bit packed coefficients then rho).
+    fn encaps_key_validation_relation() -> impl MetamorphicRelation<
+        Self::State,
+        KemInput<Self>,
+        Option<(Self::SharedSecret, Self::CipherText)>,
+    > {
+        Relation::new(
+            "Encapsulation key validation",
+            CoefficientMutator::new(
+                Self::get_pkey_from_input_as_u8,
+                Self::set_pkey_from_input_as_u8,
+                KYBER_Q,
+                Self::pkey_coefficients(),
+            ),
+            |output: &Option<(Self::SharedSecret, Self::CipherText)>,
+             _reference_output: &Option<(Self::SharedSecret, Self::CipherText)>| {
+                output.is_none()
+            },
+        )
+    }
+
+    /// Number of 12-bit coefficients packed in the public key
+    fn pkey_coefficients() -> usize {
+        (Self::PKSIZE - 32) * 8 / 12
+    }
+
+    fn encaps_key_validation_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(
+            Self::gen_input_sk_test,
+            Self::gen_state,
+            Self::call_test_encaps,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::pkey_coefficients() + 1,
+            Self::pkey_coefficients() + 1,
+            Self::LIBNAME,
+            &Self::encaps_key_validation_relation(),
+        );
+    }
+
+    /// The public key derived from the secret key must equal the generated one, and mutating the
+    /// public key embedded in the secret key must change the derived key exactly when it changes
+    /// the decapsulated secret (re-encryption check)
+    fn embedded_pkey_consistency_relation(
+    ) -> impl MetamorphicRelation<Self::State, KemInput<Self>, (Option<bool>, Self::SharedSecret)>
+    {
+        Relation::new(
+            "Embedded public key consistency",
+            BitInclusionMutator::new(
+                Self::get_embedded_pkey_from_input_as_u8,
+                Self::set_embedded_pkey_from_input_as_u8,
+            ),
+            |output: &(Option<bool>, Self::SharedSecret),
+             reference_output: &(Option<bool>, Self::SharedSecret)| {
+                reference_output.0 == Some(true) && output.0 == Some(output.1 == reference_output.1)
+            },
+        )
+    }
+
+    fn embedded_pkey_consistency_test(options: &CampaignOptions) {
+        if !Self::SUPPORTS_PUBLIC_FROM_SECRET {
+            println!(
+                "[{}] Skipping Embedded public key consistency: public key derivation unsupported",
+                Self::LIBNAME
+            );
+            return;
+        }
+        MetamorphicTarget::new(
+            Self::gen_input_sk_test,
+            Self::gen_state,
+            Self::call_test_embedded_pk,
+        )
+        .with_options(options.clone())
+        .run_relation(
+            Self::PKSIZE * 8,
+            Self::PKSIZE * 8,
+            Self::LIBNAME,
+            &Self::embedded_pkey_consistency_relation(),
+        );
+    }
+
+    fn run_tests() {
+        Self::run_tests_with_options(&CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(options: &CampaignOptions) {
+        Self::bit_inclusion_on_skey_test_with_options(options);
+        Self::encaps_key_validation_test(options);
+        Self::embedded_pkey_consistency_test(options);
+    }
+}
+
+fn hash_variant_call<Parent: HashMetamorphic, Variant: HashMetamorphic<Input = Parent::Input>>(
+    state: (Parent::State, Variant::State),
+    input: &Parent::Input,
+) -> (Vec<u8>, Vec<u8>) {
+    (
+        Parent::output_as_u8(&Parent::hash(state.0, input)),
+        Variant::output_as_u8(&Variant::hash(state.1, input)),
+    )
+}
+
+/// Check that a variant digest (e.g. Sha512/256, Blake2b with a shorter output) is not a prefix of
+/// its parent digest, as the output size is bound in the IV or parameter block
+pub fn truncated_variant_relation<
+    Parent: HashMetamorphic,
+    Variant: HashMetamorphic<Input = Parent::Input>,
+>() -> impl MetamorphicRelation<(Parent::State, Variant::State), Parent::Input, (Vec<u8>, Vec<u8>)>
+{
+    Relation::new(
+        "Truncated variant distinctness",
+        BitInclusionMutator::new(
+            Parent::input_as_u8,
+            |state: &(Parent::State, Variant::State), input, mutated| {
+                let (parent_state, mutated_input) = Parent::u8_as_input(&state.0, input, mutated);
+                ((parent_state, state.1.clone()), mutated_input)
+            },
+        ),
+        |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| {
+            !output.0.starts_with(&output.1)
+        },
+    )
+}
+
+pub fn truncated_variant_test<
+    Parent: HashMetamorphic,
+    Variant: HashMetamorphic<Input = Parent::Input>,
+>(
+    max_size: usize,
+    options: &CampaignOptions,
+) {
+    MetamorphicTarget::new(
+        Parent::gen_input,
+        || (Parent::initial_state(), Variant::initial_state()),
+        hash_variant_call::<Parent, Variant>,
+    )
+    .with_options(options.clone())
+    .run_relation(
+        1,
+        max_size,
+        &format!("{} vs {}", Variant::LIBNAME, Parent::LIBNAME),
+        &truncated_variant_relation::<Parent, Variant>(),
+    );
+}
+
+/// Check that a digest is a prefix of the XOF output of the same function (e.g. Blake3), as the
+/// XOF output stream starts with the default-length digest
+pub fn xof_prefix_relation<
+    Digest: HashMetamorphic,
+    Stream: XofMetamorphic<Input = Digest::Input>,
+>() -> impl MetamorphicRelation<(Digest::State, Stream::State), Digest::Input, (Vec<u8>, Vec<u8>)> {
+    Relation::new(
+        "XOF prefix",
+        BitInclusionMutator::new(
+            Digest::input_as_u8,
+            |state: &(Digest::State, Stream::State), input, mutated| {
+                let (digest_state, mutated_input) = Digest::u8_as_input(&state.0, input, mutated);
+                ((digest_state, state.1.clone()), mutated_input)
+            },
+        ),
+        |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| {
+            output.1.starts_with(&output.0)
+        },
+    )
+}
+
+pub fn xof_prefix_test<Digest: HashMetamorphic, Stream: XofMetamorphic<Input = Digest::Input>>(
+    max_size: usize,
+    options: &CampaignOptions,
+) -> usize {
+    MetamorphicTarget::new(
+        Digest::gen_input,
+        || (Digest::initial_state(), Stream::initial_state()),
+        |state: (Digest::State, Stream::State), input: &Digest::Input| {
+            (
+                Digest::output_as_u8(&Digest::hash(state.0, input)),
+                Stream::xof(state.1, input, &[XOF_DIFFUSION_OUTPUT_SIZE]),
+            )
+        },
+    )
+    .with_options(options.clone())
+    .run_relation(
+        1,
+        max_size,
+        &format!("{} vs {}", Digest::LIBNAME, Stream::LIBNAME),
+        &xof_prefix_relation::<Digest, Stream>(),
+    )
+}
+
+/// Static keys, party sessions and messages of a completed handshake
+#[derive(Debug, Clone)]
+pub struct KeyExchangeTranscript<K, S, I, R> {
+    pub static_keys: K,
+    pub client: S,
+    pub server: S,
+    pub init: I,
+    pub response: R,
+}
+
+pub type KeyExchangeInput<T> = KeyExchangeTranscript<
+    <T as KeyExchangeMetamorphic>::StaticKeys,
+    <T as KeyExchangeMetamorphic>::Session,
+    <T as KeyExchangeMetamorphic>::InitMessage,
+    <T as KeyExchangeMetamorphic>::ResponseMessage,
+>;
+
+/// Recorded secret, then secrets of a replayed response and of a replayed init message
+pub type ReplayOutput<T> = (
+    <T as KeyExchangeMetamorphic>::SharedSecret,
+    Option<<T as KeyExchangeMetamorphic>::SharedSecret>,
+    Option<<T as KeyExchangeMetamorphic>::SharedSecret>,
+);
+
+pub trait KeyExchangeMetamorphic: 'static {
+    type StaticKeys: std::fmt::Debug + Clone + Send + Sync;
+    type Session: std::fmt::Debug + Clone + Send + Sync;
+    type InitMessage: std::fmt::Debug + Clone + Send + Sync;
+    type ResponseMessage: std::fmt::Debug + Clone + Send + Sync;
+    type SharedSecret: std::fmt::Debug + Clone + Send + Sync + PartialEq;
+
+    const LIBNAME: &str;
+    const INITSIZE: usize;
+    const RESPONSESIZE: usize;
+
+    fn gen_static_keys() -> Self::StaticKeys;
+    fn new_session() -> Self::Session;
+
+    fn client_init(client: &mut Self::Session, keys: &Self::StaticKeys) -> Self::InitMessage;
+
+    /// Server side of the handshake, None if the init message is rejected
+    fn server_receive(
+        server: &mut Self::Session,
+        keys: &Self::StaticKeys,
+        init: &Self::InitMessage,
+    ) -> Option<Self::ResponseMessage>;
+
+    /// Client side completion of the handshake, false if the response is rejected
+    fn client_confirm(
+        client: &mut Self::Session,
+        keys: &Self::StaticKeys,
+        response: &Self::ResponseMessage,
+    ) -> bool;
+
+    fn shared_secret(session: &Self::Session) -> Self::SharedSecret;
+
+    fn get_init_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8>;
+
+    fn set_init_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        init: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>);
+
+    fn get_response_from_input_as_u8(input: &KeyExchangeInput<Self>) -> Vec<u8>;
+
+    fn set_response_from_input_as_u8(
+        _state: &(),
+        input: &KeyExchangeInput<Self>,
+        response: Vec<u8>,
+    ) -> ((), KeyExchangeInput<Self>);
+
+    /// Run an honest handshake between fresh sessions
+    fn handshake(
+        keys: &Self::StaticKeys,
+    ) -> (
+        Option<Self::SharedSecret>,
+        Option<Self::SharedSecret>,
+        KeyExchangeInput<Self>,
+    ) {
+        let mut client = Self::new_session();
+        let mut server = Self::new_session();
+        let init = Self::client_init(&mut client, keys);
+        let response = Self::server_receive(&mut server, keys, &init).unwrap();
+        let accepted = Self::client_confirm(&mut client, keys, &response);
+        (
+            accepted.then(|| Self::shared_secret(&client)),
+            Some(Self::shared_secret(&server)),
+            KeyExchangeTranscript {
+                static_keys: keys.clone(),
+                client,
+                server,
+                init,
+                response,
+            },
+        )
+    }
+
+    fn gen_input(_size: usize) -> KeyExchangeInput<Self> {
+        Self::handshake(&Self::gen_static_keys()).2
+    }
+
+    /// Client and server secrets of a new handshake using the same static keys
+    fn call_test_agreement(
+        _state: (),
+        input: &KeyExchangeInput<Self>,
+    ) -> (Option<Self::SharedSecret>, Option<Self::SharedSecret>) {
+        let (client_secret, server_secret, _) = Self::handshake(&input.static_keys);
+        (client_secret, server_secret)
+    }
+
+    /// Client and server secrets when a fresh server answers the recorded init message
+    fn call_test_init(
+        _state: (),
+        input: &KeyExchangeInput<Self>,
+    ) -> (Option<Self::SharedSecret>, Option<Self::SharedSecret>) {
+        let mut client = input.client.clone();
+        let mut server = Self::new_session();
+        match Self::server_receive(&mut server, &input.static_keys, &input.init) {
+            Some(response) => (
+                Self::client_confirm(&mut client, &input.static_keys, &response)
+                    .then(|| Self::shared_secret(&client)),
+                Some(Self::shared_secret(&server)),
+            ),
+            None => (None, None),
+        }
+    }
+
+    /// Client and server secrets when the client confirms the recorded response
+    fn call_test_response(
+        _state: (),
+        input: &KeyExchangeInput<Self>,
+    ) -> (Option<Self::SharedSecret>, Option<Self::SharedSecret>) {
+        let mut client = input.client.clone();
+        (
+            Self::client_confirm(&mut client, &input.static_keys, &input.response)
+                .then(|| Self::shared_secret(&client)),
+            Some(Self::shared_secret(&input.server)),
+        )
+    }
+
+    /// Recorded secret, then secrets of a fresh client confirming the recorded response and of a
+    /// fresh server answering the recorded init message
+    fn call_test_replay(
+        _state: (),
+        input: &KeyExchangeInput<Self>,
+    ) -> (
+        Self::SharedSecret,
+        Option<Self::SharedSecret>,
+        Option<Self::SharedSecret>,
+    ) {
+        let mut client = Self::new_session();
+        Self::client_init(&mut client, &input.static_keys);
+        let client_secret = Self::client_confirm(&mut client, &input.static_keys, &input.response)
+            .then(|| Self::shared_secret(&client));
+        let mut server = Self::new_session();
+        let server_secret = Self::server_receive(&mut server, &input.static_keys, &input.init)
+            .map(|_| Self::shared_secret(&server));
+        (
+            Self::shared_secret(&input.server),
+            client_secret,
+            server_secret,
+        )
+    }
+
+    fn agreement_relation() -> impl MetamorphicRelation<
+        (),
+        KeyExchangeInput<Self>,
+        (Option<Self::SharedSecret>, Option<Self::SharedSecret>),
+    > {
+        Relation::new("Key agreement", IdentityMutator, |output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>), _reference_output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>)| output.0.is_some() && output.0 == output.1)
+    }
+
+    fn agreement_test(iterations: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, || (), Self::call_test_agreement)
+            .with_options(options.clone())
+            .run_relation(
+                iterations,
+                iterations,
+                Self::LIBNAME,
+                &Self::agreement_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_init_relation() -> impl MetamorphicRelation<
+        (),
+        KeyExchangeInput<Self>,
+        (Option<Self::SharedSecret>, Option<Self::SharedSecret>),
+    > {
+        Relation::new("Bit Inclusion on init message", BitInclusionMutator::new(
+            Self::get_init_from_input_as_u8,
+            Self::set_init_from_input_as_u8,
+        ), |output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>), _reference_output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>)| output.0.is_none() || output.0 != output.1)
+    }
+
+    fn bit_inclusion_on_init_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, || (), Self::call_test_init)
+            .with_options(options.clone())
+            .run_relation(
+                Self::INITSIZE * 8,
+                Self::INITSIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_init_relation(),
+            );
+    }
+
+    fn bit_inclusion_on_response_relation() -> impl MetamorphicRelation<
+        (),
+        KeyExchangeInput<Self>,
+        (Option<Self::SharedSecret>, Option<Self::SharedSecret>),
+    > {
+        Relation::new("Bit Inclusion on response message", BitInclusionMutator::new(
+            Self::get_response_from_input_as_u8,
+            Self::set_response_from_input_as_u8,
+        ), |output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>), _reference_output: &(Option<Self::SharedSecret>, Option<Self::SharedSecret>)| output.0.is_none() || output.0 != output.1)
+    }
+
+    fn bit_inclusion_on_response_test(options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, || (), Self::call_test_response)
+            .with_options(options.clone())
+            .run_relation(
+                Self::RESPONSESIZE * 8,
+                Self::RESPONSESIZE * 8,
+                Self::LIBNAME,
+                &Self::bit_inclusion_on_response_relation(),
+            );
+    }
+
+    fn replay_relation() -> impl MetamorphicRelation<(), KeyExchangeInput<Self>, ReplayOutput<Self>>
+    {
+        Relation::new(
+            "Replay",
+            IdentityMutator,
+            |output: &(
+                Self::SharedSecret,
+                Option<Self::SharedSecret>,
+                Option<Self::SharedSecret>,
+            ),
+             _reference_output: &(
+                Self::SharedSecret,
+                Option<Self::SharedSecret>,
+                Option<Self::SharedSecret>,
+            )| {
+                output.1.as_ref() != Some(&output.0) && output.2.as_ref() != Some(&output.0)
+            },
+        )
+    }
+
+    fn replay_test(iterations: usize, options: &CampaignOptions) {
+        MetamorphicTarget::new(Self::gen_input, || (), Self::call_test_replay)
+            .with_options(options.clone())
+            .run_relation(
+                iterations,
+                iterations,
+                Self::LIBNAME,
+                &Self::replay_relation(),
+            );
+    }
+
+    fn run_tests(iterations: usize) {
+        Self::run_tests_with_options(iterations, &CampaignOptions::default());
+    }
+
+    fn run_tests_with_options(iterations: usize, options: &CampaignOptions) {
+        Self::agreement_test(iterations, options);
+        Self::bit_inclusion_on_init_test(options);
+        Self::bit_inclusion_on_response_test(options);
+        Self::replay_test(iterations, options);
+    }
+}
+
+/// Byte encodings produced by a derandomized KEM run, comparable across implementations
+#[derive(Debug, Clone, PartialEq)]
+pub struct KEMTranscript {
+    pub secret_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub shared_secret: Vec<u8>,
+    pub decapsulated: Vec<u8>,
+}
+
+/// Size of the `d || z || m` seed fed to derandomized KEM implementations
+pub const KEM_SEED_SIZE: usize = 96;
+
+/// Feed the same seeds to two implementations of the same KEM and check that keys, ciphertexts
+/// and shared secrets are byte-identical, returns the number of differing seeds. The seeds are
+/// derived from the campaign seed.
+///
+/// pqc_kyber implements round 3 Kyber, which hashes `m` before encryption, hashes `d` alone in
+/// keygen and derives the shared secret as `KDF(K || H(c))`, while FIPS 203 ML-KEM uses
+/// `G(d || k)` and returns `K`: transcripts of implementations of different specifications never
+/// match, their mismatches are reported as known and not counted. `kyber_ml_kem_shared_parts_test`
+/// compares the parts both specifications share instead.
+pub fn kem_differential_test<A: KEMMetamorphic, B: KEMMetamorphic>(
+    iterations: usize,
+    options: &CampaignOptions,
+) -> usize {
+    let test_name = "Differential derandomized KEM";
+    let lib_name = options.scoped(&format!("{} vs {}", A::LIBNAME, B::LIBNAME));
+    let known_mismatch = A::SPECIFICATION != B::SPECIFICATION;
+    let (mut number_of_errors, mut known_mismatches) = (0, 0);
+    for iteration in 0..iterations {
+        if options.exhausted(number_of_errors) {
+            break;
+        }
+        let mut seed = [0u8; KEM_SEED_SIZE];
+        StdRng::seed_from_u64(options.size_seed(&lib_name, test_name, iteration))
+            .fill_bytes(&mut seed);
+        let (a, b) = (
+            A::derandomized_transcript(&seed),
+            B::derandomized_transcript(&seed),
+        );
+        if a.is_some() && a == b {
+            continue;
+        }
+        if known_mismatch && a.is_some() && b.is_some() {
+            known_mismatches += 1;
+            println!(
+                "[{}] ! KNOWN ! Running {} on seed {:?}: {} and {} transcripts differ",
+                lib_name,
+                test_name,
+                seed,
+                A::SPECIFICATION,
+                B::SPECIFICATION
+            );
+            continue;
+        }
+        number_of_errors += 1;
         println!(
-            "[{}] !SUMMARY ! {} with sizes [{}-{}] : found {} errors",
-            lib_name, test_name, min_size, max_size, number_of_errors
+            "[{}] ! ERROR ! Running {} on seed {:?}: {:?}, {:?}",
+            lib_name, test_name, seed, a, b
         );
     }
+    println!(
+        "[{}] !SUMMARY ! {} with {} seeds : found {} errors and {} known mismatches",
+        lib_name, test_name, iterations, number_of_errors, known_mismatches
+    );
+    number_of_errors
 }
 
-pub trait HashMetamorphic {
-    type Input: std::fmt::Debug + Clone + Send;
-    type Output: std::fmt::Debug + Clone + Send + PartialEq;
-    type State: Clone + Send;
-
-    const LIBNAME: &str;
+fn aead_differential_call<A: AeadMetamorphic, B: AeadMetamorphic>(
+    _state: (),
+    input: &AeadInput,
+) -> (Vec<u8>, Vec<u8>) {
+    (
+        A::seal(&input.0, &input.1, &input.2, &input.3),
+        B::seal(&input.0, &input.1, &input.2, &input.3),
+    )
+}
 
-    fn initial_state() -> Self::State;
-    fn gen_input(size: usize) -> Self::Input;
-    fn input_as_u8(input: &Self::Input) -> Vec<u8>;
+/// Seal the same plaintexts with two implementations of the same AEAD and check that the
+/// ciphertexts are identical
+pub fn aead_differential_relation<A: AeadMetamorphic>(
+) -> impl MetamorphicRelation<(), AeadInput, (Vec<u8>, Vec<u8>)> {
+    Relation::new(
+        "Differential AEAD",
+        BitInclusionMutator::new(A::get_payload_from_input_as_u8, |_, input, payload| {
+            (
+                (),
+                (input.0.clone(), input.1.clone(), input.2.clone(), payload),
+            )
+        }),
+        |output: &(Vec<u8>, Vec<u8>), _reference_output: &(Vec<u8>, Vec<u8>)| output.0 == output.1,
+    )
+}
 
-    /// Return unmodified initial state and mutated as input
-    fn u8_as_input(
-        initial_state: &Self::State,
-        _initial_input: &Self::Input,
-        mutated: Vec<u8>,
-    ) -> (Self::State, Self::Input);
+pub fn aead_differential_test<A: AeadMetamorphic, B: AeadMetamorphic>(
+    max_size: usize,
+    options: &CampaignOptions,
+) {
+    assert_eq!(A::KEYSIZE, B::KEYSIZE);
+    assert_eq!(A::NONCESIZE, B::NONCESIZE);
+    MetamorphicTarget::new(A::gen_input, || (), aead_differential_call::<A, B>)
+        .with_options(options.clone())
+        .run_relation(
+            1,
+            max_size,
+            &format!("{} vs {}", A::LIBNAME, B::LIBNAME),
+            &aead_differential_relation::<A>(),
+        );
+}
 
-    fn hash_update(
-        initial_state: &Self::State,
-        _initial_input: &Self::Input,
-        first_part: &[u8],
-        second_part: &[u8],
-    ) -> (Self::State, Self::Input);
+fn signature_cross_verify_call<A: SignatureMetamorphic, B: SignatureMetamorphic>(
+    _state: (),
+    input: &SignatureInput<A::SecretKey>,
+) -> bool {
+    B::verify(&input.1, &input.2, &A::sign(&input.0, &input.2))
+}
 
-    fn output_as_u8(output: &Self::Output) -> Vec<u8>;
-    fn compare_output(initial_output: &Self::Output, output: &Self::Output) -> bool;
-    fn hash(state: Self::State, input: &Self::Input) -> Self::Output;
+/// Sign with one implementation and verify with another one of the same scheme
+pub fn signature_cross_verify_relation<A: SignatureMetamorphic>(
+) -> impl MetamorphicRelation<(), SignatureInput<A::SecretKey>, bool> {
+    Relation::new(
+        "Cross verification",
+        BitInclusionMutator::new(
+            A::get_message_from_input_as_u8,
+            A::set_message_from_input_as_u8,
+        ),
+        |output: &bool, _reference_output: &bool| *output,
+    )
+}
 
-    fn bit_inclusion_test(max_size: usize) {
-        let mutator = BitInclusionMutator::new(Self::input_as_u8, Self::u8_as_input);
-        let runner = MetamorphicTestRunner::new(
-            Self::gen_input,
-            Self::initial_state,
-            Self::hash,
-            |reference_output, output| reference_output != output,
+pub fn signature_cross_verify_test<A: SignatureMetamorphic, B: SignatureMetamorphic>(
+    max_size: usize,
+    options: &CampaignOptions,
+) {
+    MetamorphicTarget::new(A::gen_input, || (), signature_cross_verify_call::<A, B>)
+        .with_options(options.clone())
+        .run_relation(
+            1,
+            max_size,
+            &format!("{} vs {}", A::LIBNAME, B::LIBNAME),
+            &signature_cross_verify_relation::<A>(),
         );
-        runner.run_test(1, max_size, "Bit Inclusion", Self::LIBNAME, mutator);
-    }
+}
 
-    fn update_hash_test(max_size: usize) {
-        let mutator = UpdateMutator::new(Self::input_as_u8, Self::hash_update);
-        let runner = MetamorphicTestRunner::new(
-            Self::gen_input,
-            Self::initial_state,
-            Self::hash,
-            |reference_output, output| reference_output == output,
-        );
+fn gen_signature_seed_input(size: usize) -> ([u8; 32], Vec<u8>) {
+    let mut seed = [0u8; 32];
+    let mut message = vec![0u8; size];
+    campaign_rng().fill_bytes(&mut seed);
+    campaign_rng().fill_bytes(&mut message);
+    (seed, message)
+}
 
-        runner.run_test(1, max_size, "Update Hash", Self::LIBNAME, mutator);
-    }
+/// Public key and signature of each implementation, None when it rejects the seed
+pub type DeterministicSignatureOutput = (Option<(Vec<u8>, Vec<u8>)>, Option<(Vec<u8>, Vec<u8>)>);
 
-    fn run_tests(max_size: usize) {
-        Self::bit_inclusion_test(max_size);
-        Self::update_hash_test(max_size);
-    }
+fn deterministic_signature_call<A: SignatureMetamorphic, B: SignatureMetamorphic>(
+    _state: (),
+    input: &([u8; 32], Vec<u8>),
+) -> DeterministicSignatureOutput {
+    (
+        A::gen_keys_from_seed(&input.0).map(|(sk, pk)| (pk, A::sign(&sk, &input.1))),
+        B::gen_keys_from_seed(&input.0).map(|(sk, pk)| (pk, B::sign(&sk, &input.1))),
+    )
 }
 
-pub trait KEMMetamorphic {
-    type SecretKey: std::fmt::Debug + Clone + Send;
-    type PublicKey: std::fmt::Debug + Clone + Send;
-    type CipherText: std::fmt::Debug + Clone + Send;
-    type SharedSecret: std::fmt::Debug + Clone + Send + PartialEq;
-    type State: Clone + Send;
-
-    const LIBNAME: &str;
-    const PKSIZE: usize;
-    const SKSIZE: usize;
-    const CTSIZE: usize;
-    const SSSIZE: usize;
+/// Derive keys from the same seed with two deterministic implementations and check that public
+/// keys and signatures are identical
+pub fn deterministic_signature_differential_relation(
+) -> impl MetamorphicRelation<(), ([u8; 32], Vec<u8>), DeterministicSignatureOutput> {
+    Relation::new(
+        "Differential deterministic signature",
+        BitInclusionMutator::new(
+            |input: &([u8; 32], Vec<u8>)| input.1.clone(),
+            |_, input, message| ((), (input.0, message)),
+        ),
+        |output: &DeterministicSignatureOutput,
+         _reference_output: &DeterministicSignatureOutput| {
+            output.0.is_some() && output.0 == output.1
+        },
+    )
+}
 
-    fn gen_keys() -> (Self::SecretKey, Self::PublicKey);
+pub fn deterministic_signature_differential_test<
+    A: SignatureMetamorphic,
+    B: SignatureMetamorphic,
+>(
+    max_size: usize,
+    options: &CampaignOptions,
+) {
+    assert!(A::DETERMINISTIC && B::DETERMINISTIC);
+    MetamorphicTarget::new(
+        gen_signature_seed_input,
+        || (),
+        deterministic_signature_call::<A, B>,
+    )
+    .with_options(options.clone())
+    .run_relation(
+        1,
+        max_size,
+        &format!("{} vs {}", A::LIBNAME, B::LIBNAME),
+        &deterministic_signature_differential_relation(),
+    );
+}
 
-    fn decaps(sk: &Self::SecretKey, ct: &Self::CipherText) -> Self::SharedSecret;
-    fn encaps(pk: &Self::PublicKey) -> (Self::SharedSecret, Self::CipherText);
+fn gen_random_bytes(size: usize) -> Vec<u8> {
+    let mut data = vec![0u8; size];
+    campaign_rng().fill_bytes(&mut data);
+    data
+}
 
-    fn gen_input_sk_test(_msg_size: usize) -> (Self::SecretKey, Self::PublicKey, Self::CipherText) {
-        let (sk, pk) = Self::gen_keys();
-        let res = Self::encaps(&pk);
-        (sk, pk, res.1)
-    }
+/// Run the bit inclusion and update relations on a hash configured at runtime, e.g. with a key,
+/// a personalization string or an output size captured by the closures: `new_state` creates the
+/// configured hasher, `update` absorbs data and `finalize` returns the digest
+pub fn configured_hash_test<S: Clone + Send + Sync + 'static>(
+    lib_name: &str,
+    new_state: impl Fn() -> S + Send + Sync + 'static,
+    update: impl Fn(&mut S, &[u8]) + Send + Sync + 'static,
+    finalize: impl Fn(S) -> Vec<u8> + Send + Sync + 'static,
+    max_size: usize,
+    options: &CampaignOptions,
+) -> usize {
+    let update = Arc::new(update);
+    let call_update = Arc::clone(&update);
+    let target = MetamorphicTarget::new(
+        gen_random_bytes,
+        new_state,
+        move |mut state: S, input: &Vec<u8>| {
+            call_update(&mut state, input);
+            finalize(state)
+        },
+    )
+    .with_options(options.clone());
 
-    fn gen_input_pk_test(_msg_size: usize) -> (Self::SecretKey, Self::PublicKey) {
-        Self::gen_keys()
-    }
+    let bit_inclusion = Relation::new("Bit Inclusion", BitFlipMutator::whole(), outputs_differ);
+    let update_hash = Relation::new(
+        "Update Hash",
+        UpdateMutator::new(
+            |input: &Vec<u8>| input.clone(),
+            move |state: &S, _input: &Vec<u8>, first_part: &[u8], second_part: &[u8]| {
+                let mut state = state.clone();
+                update(&mut state, first_part);
+                (state, second_part.to_vec())
+            },
+        ),
+        outputs_equal,
+    );
 
-    fn gen_state() -> Self::State;
+    target.run_relation(1, max_size, lib_name, &bit_inclusion)
+        + target.run_relation(1, max_size, lib_name, &update_hash)
+}
 
-    fn get_skey_from_input_as_u8(
-        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
-    ) -> Vec<u8>;
+/// Extract the mutated bytes of an input
+type ExtractFn<I> = Arc<dyn Fn(&I) -> Vec<u8> + Send + Sync>;
+/// Include mutated bytes in an input, returning the follow-up state and input
+type IncludeFn<I, S> = Arc<dyn Fn(&S, &I, Vec<u8>) -> (S, I) + Send + Sync>;
+/// Include bytes split in two parts, the first one absorbed in the state
+type IncludeSplitFn<I, S> = Arc<dyn Fn(&S, &I, &[u8], &[u8]) -> (S, I) + Send + Sync>;
+/// Derive the follow-up state and input of a mutation index
+type IndexFn<I, S> = Arc<dyn Fn(&S, &I, usize) -> (S, I) + Send + Sync>;
+/// Derive the follow-up state and input from a RNG
+type RandomFn<I, S> = Arc<dyn Fn(&S, &I, &mut StdRng) -> (S, I) + Send + Sync>;
+/// Byte view of an input, mutated in place
+type BytesFn<I> = Arc<dyn Fn(&mut I) -> &mut [u8] + Send + Sync>;
+/// Byte view of an input, None when the input has no such view
+type MaybeBytesFn<I> = Arc<dyn Fn(&mut I) -> Option<&mut [u8]> + Send + Sync>;
 
-    fn get_pkey_from_input_as_u8(
-        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
-    ) -> Vec<u8>;
+pub trait Mutator<I: Clone, S: Clone>: Clone {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I);
 
-    fn set_skey_from_input_as_u8(
-        _state: &Self::State,
-        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
-        key: Vec<u8>,
-    ) -> (
-        Self::State,
-        (Self::SecretKey, Self::PublicKey, Self::CipherText),
-    );
+    /// Mutate `input` in place, keeping the initial state. Returns false when the mutator has no
+    /// in-place path, `mutate_input` is used instead
+    fn mutate_in_place(&self, _input: &mut I, _element_to_mutate: usize) -> bool {
+        false
+    }
 
-    fn set_pkey_from_input_as_u8(
-        _state: &Self::State,
-        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
-        key: Vec<u8>,
-    ) -> (
-        Self::State,
-        (Self::SecretKey, Self::PublicKey, Self::CipherText),
-    );
-    fn output_as_u8(output: Self::SharedSecret) -> Vec<u8>;
+    /// Restore an input mutated by `mutate_in_place`
+    fn undo_in_place(&self, _input: &mut I, _element_to_mutate: usize) {}
 
-    fn call_test_sk(
-        _state: Self::State,
-        input: &(Self::SecretKey, Self::PublicKey, Self::CipherText),
-    ) -> Self::SharedSecret {
-        Self::decaps(&input.0, &input.2)
+    /// Seed of the randomness used by the mutations, reported along with their errors so that
+    /// they can be replayed along with the mutation index
+    fn seed(&self, _element_to_mutate: usize) -> Option<u64> {
+        None
     }
 
-    fn bit_inclusion_on_skey_test() {
-        let mutator = BitInclusionMutator::new(
-            Self::get_skey_from_input_as_u8,
-            Self::set_skey_from_input_as_u8,
-        );
-        let runner = MetamorphicTestRunner::new(
-            Self::gen_input_sk_test,
-            Self::gen_state,
-            Self::call_test_sk,
-            |reference_output, output| reference_output != output,
-        );
-        runner.run_test(
-            Self::SKSIZE * 8,
-            Self::SKSIZE * 8,
-            "Bit Inclusion on secret key",
-            Self::LIBNAME,
-            mutator,
-        );
+    /// Mutator drawing its randomness from `seed`, set by the runner from the campaign seed
+    fn reseed(self, _seed: u64) -> Self {
+        self
     }
 
-    fn run_tests() {
-        Self::bit_inclusion_on_skey_test();
+    /// Apply `other` on the output of this mutator, with the same element to mutate
+    fn then<M: Mutator<I, S>>(self, other: M) -> Compose<Self, M> {
+        Compose(self, other)
     }
 }
 
-pub trait Mutator<I: Clone, S: Clone>: Clone {
-    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I);
+#[derive(Clone)]
+pub struct Compose<A, B>(A, B);
+
+impl<I: Clone, S: Clone, A: Mutator<I, S>, B: Mutator<I, S>> Mutator<I, S> for Compose<A, B> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let (state, input) = self.0.mutate_input(input, initial_state, element_to_mutate);
+        self.1.mutate_input(&input, &state, element_to_mutate)
+    }
+
+    fn seed(&self, element_to_mutate: usize) -> Option<u64> {
+        self.0
+            .seed(element_to_mutate)
+            .or_else(|| self.1.seed(element_to_mutate))
+    }
+
+    fn reseed(self, seed: u64) -> Self {
+        Compose(self.0.reseed(seed), self.1.reseed(seed))
+    }
 }
 
 #[derive(Clone)]
 pub struct BitInclusionMutator<I: Clone, S: Clone> {
-    extract_mutable: fn(&I) -> Vec<u8>,
-    include_mutated: fn(&S, &I, Vec<u8>) -> (S, I),
+    extract_mutable: ExtractFn<I>,
+    include_mutated: IncludeFn<I, S>,
+    /// Byte view of the mutable part, to flip its bits in place when there is one
+    bytes_mut: Option<MaybeBytesFn<I>>,
 }
 
 impl<I: Clone, S: Clone> BitInclusionMutator<I, S> {
-    fn new(
-        extract_mutable: fn(&I) -> Vec<u8>,
-        include_mutated: fn(&S, &I, Vec<u8>) -> (S, I),
+    pub fn new(
+        extract_mutable: impl Fn(&I) -> Vec<u8> + Send + Sync + 'static,
+        include_mutated: impl Fn(&S, &I, Vec<u8>) -> (S, I) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            extract_mutable: Arc::new(extract_mutable),
+            include_mutated: Arc::new(include_mutated),
+            bytes_mut: None,
+        }
+    }
+
+    /// Flip the bits in place on the inputs for which `bytes_mut` returns the mutable part, and
+    /// through the extract and include functions on the others
+    pub fn with_bytes_mut(
+        self,
+        bytes_mut: impl Fn(&mut I) -> Option<&mut [u8]> + Send + Sync + 'static,
     ) -> Self {
         Self {
-            extract_mutable,
-            include_mutated,
+            bytes_mut: Some(Arc::new(bytes_mut)),
+            ..self
         }
     }
 }
@@ -298,22 +4058,86 @@ impl<I: Clone, S: Clone> Mutator<I, S> for BitInclusionMutator<I, S> {
         flip_one_bit(&mut mutable_part, element_to_mutate);
         (self.include_mutated)(initial_state, input, mutable_part)
     }
+
+    fn mutate_in_place(&self, input: &mut I, element_to_mutate: usize) -> bool {
+        match self
+            .bytes_mut
+            .as_ref()
+            .and_then(|bytes_mut| bytes_mut(input))
+        {
+            Some(bytes) => {
+                flip_one_bit(bytes, element_to_mutate);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn undo_in_place(&self, input: &mut I, element_to_mutate: usize) {
+        if let Some(bytes) = self
+            .bytes_mut
+            .as_ref()
+            .and_then(|bytes_mut| bytes_mut(input))
+        {
+            flip_one_bit(bytes, element_to_mutate);
+        }
+    }
+}
+
+/// Flip one bit of a byte view of the input, in place on the runner buffer: no copy of the input
+/// is made for each mutation
+#[derive(Clone)]
+pub struct BitFlipMutator<I: Clone> {
+    bytes: BytesFn<I>,
+}
+
+impl<I: Clone> BitFlipMutator<I> {
+    /// `bytes` selects the mutated bytes of the input, e.g. one key of a tuple
+    pub fn new(bytes: impl Fn(&mut I) -> &mut [u8] + Send + Sync + 'static) -> Self {
+        Self {
+            bytes: Arc::new(bytes),
+        }
+    }
+}
+
+impl<I: Clone + AsMut<[u8]>> BitFlipMutator<I> {
+    /// Flip the bits of the whole input, a `Vec<u8>` or a byte array
+    pub fn whole() -> Self {
+        Self::new(|input: &mut I| input.as_mut())
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for BitFlipMutator<I> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let mut mutated = input.clone();
+        flip_one_bit((self.bytes)(&mut mutated), element_to_mutate);
+        (initial_state.clone(), mutated)
+    }
+
+    fn mutate_in_place(&self, input: &mut I, element_to_mutate: usize) -> bool {
+        flip_one_bit((self.bytes)(input), element_to_mutate);
+        true
+    }
+
+    fn undo_in_place(&self, input: &mut I, element_to_mutate: usize) {
+        flip_one_bit((self.bytes)(input), element_to_mutate);
+    }
 }
 
 #[derive(Clone)]
 pub struct UpdateMutator<I: Clone, S: Clone> {
-    extract_mutable: fn(&I) -> Vec<u8>,
-    include_mutated: fn(&S, &I, &[u8], &[u8]) -> (S, I),
+    extract_mutable: ExtractFn<I>,
+    include_mutated: IncludeSplitFn<I, S>,
 }
 
 impl<I: Clone, S: Clone> UpdateMutator<I, S> {
-    fn new(
-        extract_mutable: fn(&I) -> Vec<u8>,
-        include_mutated: fn(&S, &I, &[u8], &[u8]) -> (S, I),
+    pub fn new(
+        extract_mutable: impl Fn(&I) -> Vec<u8> + Send + Sync + 'static,
+        include_mutated: impl Fn(&S, &I, &[u8], &[u8]) -> (S, I) + Send + Sync + 'static,
     ) -> Self {
         Self {
-            extract_mutable,
-            include_mutated,
+            extract_mutable: Arc::new(extract_mutable),
+            include_mutated: Arc::new(include_mutated),
         }
     }
 }
@@ -330,6 +4154,246 @@ impl<I: Clone, S: Clone> Mutator<I, S> for UpdateMutator<I, S> {
     }
 }
 
+/// Apply an indexed transformation to the input, e.g. changing the n-th parameter
+#[derive(Clone)]
+pub struct IndexMutator<I: Clone, S: Clone> {
+    mutate: IndexFn<I, S>,
+}
+
+impl<I: Clone, S: Clone> IndexMutator<I, S> {
+    pub fn new(mutate: impl Fn(&S, &I, usize) -> (S, I) + Send + Sync + 'static) -> Self {
+        Self {
+            mutate: Arc::new(mutate),
+        }
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for IndexMutator<I, S> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        (self.mutate)(initial_state, input, element_to_mutate)
+    }
+}
+
+/// Apply a randomized transformation to the input, e.g. drawing read lengths. The randomness is
+/// drawn from a RNG seeded with `seed` plus the mutation index
+#[derive(Clone)]
+pub struct RandomMutator<I: Clone, S: Clone> {
+    mutate: RandomFn<I, S>,
+    seed: u64,
+}
+
+impl<I: Clone, S: Clone> RandomMutator<I, S> {
+    /// Mutator with a random seed, that runners replace with one derived from the campaign seed.
+    /// Use `with_seed` to replay a reported error
+    pub fn new(mutate: impl Fn(&S, &I, &mut StdRng) -> (S, I) + Send + Sync + 'static) -> Self {
+        Self {
+            mutate: Arc::new(mutate),
+            seed: rand::random(),
+        }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for RandomMutator<I, S> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(element_to_mutate as u64));
+        (self.mutate)(initial_state, input, &mut rng)
+    }
+
+    fn seed(&self, _element_to_mutate: usize) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn reseed(self, seed: u64) -> Self {
+        self.with_seed(seed)
+    }
+}
+
+/// Largest offset at which the AlignmentMutator places the data, covers SIMD register widths
+const MAX_ALIGNMENT_OFFSET: usize = 64;
+
+/// Copy the data at an offset from a `MAX_ALIGNMENT_OFFSET` boundary and include it from there
+#[derive(Clone)]
+pub struct AlignmentMutator<I: Clone, S: Clone> {
+    extract_mutable: ExtractFn<I>,
+    include_mutated: IncludeSplitFn<I, S>,
+}
+
+impl<I: Clone, S: Clone> AlignmentMutator<I, S> {
+    pub fn new(
+        extract_mutable: impl Fn(&I) -> Vec<u8> + Send + Sync + 'static,
+        include_mutated: impl Fn(&S, &I, &[u8], &[u8]) -> (S, I) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            extract_mutable: Arc::new(extract_mutable),
+            include_mutated: Arc::new(include_mutated),
+        }
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for AlignmentMutator<I, S> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let mutable_part = (self.extract_mutable)(input);
+        // The allocator only guarantees the alignment of u8, the start of the buffer is moved to
+        // the next boundary so that the offset is the actual misalignment
+        let mut buffer = vec![0u8; 2 * MAX_ALIGNMENT_OFFSET + mutable_part.len()];
+        let start = buffer.as_ptr().align_offset(MAX_ALIGNMENT_OFFSET)
+            + element_to_mutate % MAX_ALIGNMENT_OFFSET;
+        let data = &mut buffer[start..start + mutable_part.len()];
+        data.copy_from_slice(&mutable_part);
+        (self.include_mutated)(initial_state, input, data, &[])
+    }
+}
+
+/// Read source returning at most `max_read` bytes, and a random amount below it, per call
+struct ShortReader<'a> {
+    data: &'a [u8],
+    max_read: usize,
+    rng: StdRng,
+}
+
+impl Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let limit = self.max_read.min(self.data.len()).min(buf.len());
+        if limit == 0 {
+            return Ok(0);
+        }
+        let size = self.rng.gen_range(1..=limit);
+        buf[..size].copy_from_slice(&self.data[..size]);
+        self.data = &self.data[size..];
+        Ok(size)
+    }
+}
+
+/// Write sink including every written buffer as a first part
+struct IncludeWriter<I, S> {
+    state: S,
+    input: I,
+    include_mutated: IncludeSplitFn<I, S>,
+}
+
+impl<I, S> Write for IncludeWriter<I, S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let (state, _) = (self.include_mutated)(&self.state, &self.input, buf, &[]);
+        self.state = state;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Stream the data with `io::copy` from a source with short reads of at most
+/// `element_to_mutate` bytes, including each read separately. The read sizes are drawn from a
+/// RNG seeded with `seed` plus the mutation index
+#[derive(Clone)]
+pub struct ShortReadMutator<I: Clone, S: Clone> {
+    extract_mutable: ExtractFn<I>,
+    include_mutated: IncludeSplitFn<I, S>,
+    seed: u64,
+}
+
+impl<I: Clone, S: Clone> ShortReadMutator<I, S> {
+    /// Mutator with a random seed, that runners replace with one derived from the campaign seed.
+    /// Use `with_seed` to replay a reported error
+    pub fn new(
+        extract_mutable: impl Fn(&I) -> Vec<u8> + Send + Sync + 'static,
+        include_mutated: impl Fn(&S, &I, &[u8], &[u8]) -> (S, I) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            extract_mutable: Arc::new(extract_mutable),
+            include_mutated: Arc::new(include_mutated),
+            seed: rand::random(),
+        }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for ShortReadMutator<I, S> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let mutable_part = (self.extract_mutable)(input);
+        let mut reader = ShortReader {
+            data: &mutable_part,
+            max_read: element_to_mutate,
+            rng: StdRng::seed_from_u64(self.seed.wrapping_add(element_to_mutate as u64)),
+        };
+        let mut writer = IncludeWriter {
+            state: initial_state.clone(),
+            input: input.clone(),
+            include_mutated: Arc::clone(&self.include_mutated),
+        };
+        std::io::copy(&mut reader, &mut writer).unwrap();
+        (self.include_mutated)(&writer.state, input, &[], &[])
+    }
+
+    fn seed(&self, _element_to_mutate: usize) -> Option<u64> {
+        Some(self.seed)
+    }
+
+    fn reseed(self, seed: u64) -> Self {
+        self.with_seed(seed)
+    }
+}
+
+/// Leave the input untouched, repeating a randomized call on it
+#[derive(Clone)]
+pub struct IdentityMutator;
+
+impl<I: Clone, S: Clone> Mutator<I, S> for IdentityMutator {
+    fn mutate_input(&self, input: &I, initial_state: &S, _element_to_mutate: usize) -> (S, I) {
+        (initial_state.clone(), input.clone())
+    }
+}
+
+/// Write a value to one packed coefficient: runner positions start at 1, position n writes the
+/// coefficient n - 1, so that sizes of `coefficients + 1` cover each coefficient once
+#[derive(Clone)]
+pub struct CoefficientMutator<I: Clone, S: Clone> {
+    extract_mutable: ExtractFn<I>,
+    include_mutated: IncludeFn<I, S>,
+    /// Value written to the mutated coefficient
+    value: u16,
+    /// Number of packed coefficients
+    coefficients: usize,
+}
+
+impl<I: Clone, S: Clone> CoefficientMutator<I, S> {
+    pub fn new(
+        extract_mutable: impl Fn(&I) -> Vec<u8> + Send + Sync + 'static,
+        include_mutated: impl Fn(&S, &I, Vec<u8>) -> (S, I) + Send + Sync + 'static,
+        value: u16,
+        coefficients: usize,
+    ) -> Self {
+        Self {
+            extract_mutable: Arc::new(extract_mutable),
+            include_mutated: Arc::new(include_mutated),
+            value,
+            coefficients,
+        }
+    }
+}
+
+impl<I: Clone, S: Clone> Mutator<I, S> for CoefficientMutator<I, S> {
+    fn mutate_input(&self, input: &I, initial_state: &S, element_to_mutate: usize) -> (S, I) {
+        let mut mutable_part = (self.extract_mutable)(input);
+        if element_to_mutate == 0 || element_to_mutate > self.coefficients {
+            panic!("index error");
+        }
+        set_coefficient_12bit(&mut mutable_part, element_to_mutate - 1, self.value);
+        (self.include_mutated)(initial_state, input, mutable_part)
+    }
+}
+
+/// Kyber / ML-KEM modulus
+pub const KYBER_Q: u16 = 3329;
+
 pub fn set_one_bit(input: &mut [u8], idx: usize, value: bool) {
     if idx >= input.len() * 8 {
         panic!("index error");
@@ -347,3 +4411,18 @@ pub fn flip_one_bit(input: &mut [u8], idx: usize) {
     }
     input[idx / 8] ^= 1 << (7 - (idx % 8));
 }
+
+/// Overwrite the idx-th coefficient of a FIPS 203 ByteEncode12 packed array
+pub fn set_coefficient_12bit(input: &mut [u8], idx: usize, value: u16) {
+    if (idx / 2) * 3 + 2 >= input.len() || value >= 1 << 12 {
+        panic!("index error");
+    }
+    let offset = (idx / 2) * 3;
+    if idx % 2 == 1 {
+        input[offset + 1] = (input[offset + 1] & 0x0f) | ((value & 0x0f) << 4) as u8;
+        input[offset + 2] = (value >> 4) as u8;
+    } else {
+        input[offset] = value as u8;
+        input[offset + 1] = (input[offset + 1] & 0xf0) | (value >> 8) as u8;
+    }
+}
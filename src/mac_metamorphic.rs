@@ -0,0 +1,124 @@
+use crate::MacMetamorphic;
+use blake2::{Blake2bMac512, Blake2sMac256};
+use blake3::Hasher;
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use sha3::Sha3_256;
+
+macro_rules! impl_mac_metamorphic {
+    ($mac_type:ty, $test_struct_name:ident, $libname:literal, $keysize:literal, $tagsize:literal) => {
+        pub struct $test_struct_name {}
+        impl MacMetamorphic for $test_struct_name {
+            type State = $mac_type;
+
+            const LIBNAME: &str = $libname;
+
+            const KEYSIZE: usize = $keysize;
+
+            const TAGSIZE: usize = $tagsize;
+
+            fn new_state(key: &[u8]) -> Self::State {
+                <Self::State as Mac>::new_from_slice(key).unwrap()
+            }
+
+            fn update(state: &mut Self::State, data: &[u8]) {
+                state.update(data);
+            }
+
+            fn finalize(state: Self::State) -> Vec<u8> {
+                state.finalize().into_bytes().to_vec()
+            }
+
+            fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool {
+                let mut state = Self::new_state(key);
+                state.update(message);
+                state.verify_slice(tag).is_ok()
+            }
+        }
+    };
+}
+
+impl_mac_metamorphic! {Hmac<Sha256>, HmacSha256Metamorphic, "Hmac Sha256", 32, 32}
+impl_mac_metamorphic! {Hmac<Sha512>, HmacSha512Metamorphic, "Hmac Sha512", 64, 64}
+impl_mac_metamorphic! {Hmac<Sha3_256>, HmacSha3_256Metamorphic, "Hmac Sha3_256", 32, 32}
+impl_mac_metamorphic! {Blake2bMac512, Blake2bMac512Metamorphic, "Blake2bMac512", 64, 64}
+impl_mac_metamorphic! {Blake2sMac256, Blake2sMac256Metamorphic, "Blake2sMac256", 32, 32}
+
+pub struct Blake3KeyedMetamorphic {}
+impl MacMetamorphic for Blake3KeyedMetamorphic {
+    type State = Hasher;
+
+    const LIBNAME: &str = "Blake3 keyed";
+
+    const KEYSIZE: usize = blake3::KEY_LEN;
+
+    const TAGSIZE: usize = blake3::OUT_LEN;
+
+    fn new_state(key: &[u8]) -> Self::State {
+        Hasher::new_keyed(key.try_into().unwrap())
+    }
+
+    fn update(state: &mut Self::State, data: &[u8]) {
+        state.update(data);
+    }
+
+    fn finalize(state: Self::State) -> Vec<u8> {
+        state.finalize().as_bytes().to_vec()
+    }
+
+    fn mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+        blake3::keyed_hash(key.try_into().unwrap(), message)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool {
+        // blake3::Hash equality is constant time
+        match <[u8; blake3::OUT_LEN]>::try_from(tag) {
+            Ok(tag) => blake3::keyed_hash(key.try_into().unwrap(), message) == tag,
+            Err(_) => false,
+        }
+    }
+}
+
+macro_rules! impl_mac_metamorphic_ring {
+    ($hmac_algorithm:expr, $test_struct_name:ident, $libname:literal, $keysize:literal, $tagsize:literal) => {
+        pub struct $test_struct_name {}
+        impl MacMetamorphic for $test_struct_name {
+            type State = ring::hmac::Context;
+
+            const LIBNAME: &str = $libname;
+
+            const KEYSIZE: usize = $keysize;
+
+            const TAGSIZE: usize = $tagsize;
+
+            fn new_state(key: &[u8]) -> Self::State {
+                ring::hmac::Context::with_key(&ring::hmac::Key::new($hmac_algorithm, key))
+            }
+
+            fn update(state: &mut Self::State, data: &[u8]) {
+                state.update(data);
+            }
+
+            fn finalize(state: Self::State) -> Vec<u8> {
+                state.sign().as_ref().to_vec()
+            }
+
+            fn mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+                ring::hmac::sign(&ring::hmac::Key::new($hmac_algorithm, key), message)
+                    .as_ref()
+                    .to_vec()
+            }
+
+            fn verify(key: &[u8], message: &[u8], tag: &[u8]) -> bool {
+                ring::hmac::verify(&ring::hmac::Key::new($hmac_algorithm, key), message, tag)
+                    .is_ok()
+            }
+        }
+    };
+}
+
+impl_mac_metamorphic_ring! {ring::hmac::HMAC_SHA256, RingHmacSha256Metamorphic, "Ring Hmac SHA256", 32, 32}
+impl_mac_metamorphic_ring! {ring::hmac::HMAC_SHA384, RingHmacSha384Metamorphic, "Ring Hmac SHA384", 48, 48}
+impl_mac_metamorphic_ring! {ring::hmac::HMAC_SHA512, RingHmacSha512Metamorphic, "Ring Hmac SHA512", 64, 64}
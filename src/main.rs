@@ -1,73 +1,323 @@
+use metamorphic_testing_rs::aead_differential_test;
+use metamorphic_testing_rs::aead_metamorphic::{
+    Aes128GcmMetamorphic, Aes256GcmMetamorphic, ChaCha20Poly1305Metamorphic,
+    RingAes128GcmMetamorphic, RingAes256GcmMetamorphic, RingChaCha20Poly1305Metamorphic,
+    XChaCha20Poly1305Metamorphic,
+};
+use metamorphic_testing_rs::cipher_metamorphic::{
+    Aes128CbcMetamorphic, Aes128CtrMetamorphic, Aes128EcbMetamorphic, Aes256CbcMetamorphic,
+    Aes256CtrMetamorphic, Aes256EcbMetamorphic, ChaCha20Metamorphic,
+};
 use metamorphic_testing_rs::hash_metamorphic::{
-    AsconMetamorphic, BeltMetamorphic, Blake2b512Metamorphic, Blake2s256Metamorphic,
-    Blake3Metamorphic, Fsb160Metamorphic, Fsb224Metamorphic, Fsb256Metamorphic, Fsb384Metamorphic,
-    Fsb512Metamorphic, Groestl224Metamorphic, Groestl256Metamorphic, Groestl384Metamorphic,
-    Groestl512Metamorphic, Jh224Metamorphic, Jh256Metamorphic, Jh384Metamorphic, Jh512Metamorphic,
-    RingSHA256Metamorphic, RingSHA384Metamorphic, RingSHA512Metamorphic, RingSHA512_256Metamorphic,
-    Ripemd128Metamorphic, Ripemd160Metamorphic, Ripemd256Metamorphic, Ripemd320Metamorphic,
-    Sha2_224Metamorphic, Sha2_256Metamorphic, Sha2_384Metamorphic, Sha2_512Metamorphic,
-    Sha2_512_224Metamorphic, Sha2_512_256Metamorphic, Sha3_224Metamorphic, Sha3_256Metamorphic,
-    Sha3_384Metamorphic, Sha3_512Metamorphic, Shabal192Metamorphic, Shabal224Metamorphic,
-    Shabal256Metamorphic, Shabal384Metamorphic, Shabal512Metamorphic, Skein1024Metamorphic,
-    Skein256Metamorphic, Skein512Metamorphic, Sm3Metamorphic, TigerMetamorphic,
+    blake2b_personalized_test, blake2b_var_distinctness_test, blake2b_var_test,
+    skein512_output_size_test, AsconMetamorphic, BeltMetamorphic, Blake2b256Metamorphic,
+    Blake2b512Metamorphic, Blake2s256Metamorphic, Blake3Metamorphic, Fsb160Metamorphic,
+    Fsb224Metamorphic, Fsb256Metamorphic, Fsb384Metamorphic, Fsb512Metamorphic,
+    Groestl224Metamorphic, Groestl256Metamorphic, Groestl384Metamorphic, Groestl512Metamorphic,
+    Jh224Metamorphic, Jh256Metamorphic, Jh384Metamorphic, Jh512Metamorphic, RingSHA256Metamorphic,
+    RingSHA384Metamorphic, RingSHA512Metamorphic, RingSHA512_256Metamorphic, Ripemd128Metamorphic,
+    Ripemd160Metamorphic, Ripemd256Metamorphic, Ripemd320Metamorphic, Sha2_224Metamorphic,
+    Sha2_256Metamorphic, Sha2_384Metamorphic, Sha2_512Metamorphic, Sha2_512_224Metamorphic,
+    Sha2_512_256Metamorphic, Sha3_224Metamorphic, Sha3_256Metamorphic, Sha3_384Metamorphic,
+    Sha3_512Metamorphic, Shabal192Metamorphic, Shabal224Metamorphic, Shabal256Metamorphic,
+    Shabal384Metamorphic, Shabal512Metamorphic, Skein1024Metamorphic, Skein256Metamorphic,
+    Skein512Metamorphic, Skein512_512Metamorphic, Sm3Metamorphic, TigerMetamorphic,
     WhirlpoolMetamorphic,
 };
-use metamorphic_testing_rs::kem_metamorphic::PQCKyberMetamorphic;
+use metamorphic_testing_rs::kdf_metamorphic::{
+    Blake3DeriveKeyMetamorphic, RingHkdfSha256Metamorphic, RingHkdfSha384Metamorphic,
+    RingHkdfSha512Metamorphic,
+};
+use metamorphic_testing_rs::kem_differential_test;
+use metamorphic_testing_rs::kem_metamorphic::{
+    kyber_ml_kem_shared_parts_test, MlKem1024Metamorphic, MlKem512Metamorphic, MlKem768Metamorphic,
+    PQCKyberMetamorphic,
+};
+use metamorphic_testing_rs::kex_metamorphic::{PQCKyberAkeMetamorphic, PQCKyberUakeMetamorphic};
+use metamorphic_testing_rs::key_agreement_metamorphic::{
+    EcdhP256Metamorphic, RingEcdhP256Metamorphic, RingX25519Metamorphic, X25519DalekMetamorphic,
+};
+use metamorphic_testing_rs::mac_metamorphic::{
+    Blake2bMac512Metamorphic, Blake2sMac256Metamorphic, Blake3KeyedMetamorphic,
+    HmacSha256Metamorphic, HmacSha3_256Metamorphic, HmacSha512Metamorphic,
+    RingHmacSha256Metamorphic, RingHmacSha384Metamorphic, RingHmacSha512Metamorphic,
+};
+use metamorphic_testing_rs::mutation_analysis::hash_mutation_analysis;
+use metamorphic_testing_rs::password_hash_metamorphic::{
+    Argon2idMetamorphic, BcryptMetamorphic, Pbkdf2Sha256Metamorphic, RingPbkdf2Sha256Metamorphic,
+    ScryptMetamorphic,
+};
+use metamorphic_testing_rs::signature_metamorphic::{
+    EcdsaP256Metamorphic, Ed25519DalekMetamorphic, MlDsa44Metamorphic, MlDsa65Metamorphic,
+    MlDsa87Metamorphic, RingEcdsaP256Metamorphic, RingEd25519Metamorphic,
+};
+use metamorphic_testing_rs::truncated_variant_test;
+use metamorphic_testing_rs::xof_metamorphic::{
+    Blake3XofMetamorphic, CShake128Metamorphic, CShake256Metamorphic, KangarooTwelveMetamorphic,
+    Shake128Metamorphic, Shake256Metamorphic, TurboShake128Metamorphic, TurboShake256Metamorphic,
+};
+use metamorphic_testing_rs::xof_prefix_test;
+use metamorphic_testing_rs::AeadMetamorphic;
+use metamorphic_testing_rs::CampaignOptions;
+use metamorphic_testing_rs::CipherMetamorphic;
+use metamorphic_testing_rs::GeneratedKeyAgreementMetamorphic;
 use metamorphic_testing_rs::HashMetamorphic;
 use metamorphic_testing_rs::KEMMetamorphic;
+use metamorphic_testing_rs::KdfMetamorphic;
+use metamorphic_testing_rs::KeyAgreementMetamorphic;
+use metamorphic_testing_rs::KeyExchangeMetamorphic;
+use metamorphic_testing_rs::MacMetamorphic;
+use metamorphic_testing_rs::PasswordHashMetamorphic;
+use metamorphic_testing_rs::SignatureMetamorphic;
+use metamorphic_testing_rs::XofMetamorphic;
+use metamorphic_testing_rs::{
+    deterministic_signature_differential_test, signature_cross_verify_test,
+};
+use skein::consts::U48;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Non-negative integer read from the environment variable `name`, if set
+fn var<T: FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{name} must be a non-negative integer, got {value:?}"))
+    })
+}
+
+/// Campaign limits from the environment: METAMORPHIC_TIME_BUDGET (seconds), METAMORPHIC_MAX_FINDINGS
+/// (per relation run), METAMORPHIC_SAMPLED_MUTATIONS (mutation positions per input size),
+/// METAMORPHIC_SEED (replays a campaign) and METAMORPHIC_CHECKPOINT (progress file to resume from)
+fn campaign_options_from_env() -> CampaignOptions {
+    let mut options = CampaignOptions::new();
+    if let Some(seed) = var("METAMORPHIC_SEED") {
+        options = options.with_seed(seed);
+    }
+    if let Some(seconds) = var("METAMORPHIC_TIME_BUDGET") {
+        options = options.with_time_budget(Duration::from_secs(seconds));
+    }
+    if let Some(max_findings) = var("METAMORPHIC_MAX_FINDINGS") {
+        options = options.with_max_findings(max_findings);
+    }
+    if let Some(sampled_mutations) = var("METAMORPHIC_SAMPLED_MUTATIONS") {
+        options = options.with_sampled_mutations(sampled_mutations);
+    }
+    if let Ok(path) = std::env::var("METAMORPHIC_CHECKPOINT") {
+        options = options.with_checkpoint(&path).unwrap_or_else(|error| {
+            panic!("METAMORPHIC_CHECKPOINT {path:?} cannot be opened: {error}")
+        });
+    }
+    options
+}
 
 fn main() {
+    let options = campaign_options_from_env();
+    println!("Campaign seed: {}", options.seed());
+
     // This test uncover errors
-    PQCKyberMetamorphic::run_tests();
+    PQCKyberMetamorphic::run_tests_with_options(&options);
+    MlKem512Metamorphic::run_tests_with_options(&options);
+    MlKem768Metamorphic::run_tests_with_options(&options);
+    MlKem1024Metamorphic::run_tests_with_options(&options);
+    kyber_ml_kem_shared_parts_test(100, &options);
+    // Round 3 Kyber and FIPS 203 transcripts differ by design, mismatches are reported as known
+    kem_differential_test::<PQCKyberMetamorphic, MlKem768Metamorphic>(100, &options);
+    PQCKyberUakeMetamorphic::run_tests_with_options(1000, &options);
+    PQCKyberAkeMetamorphic::run_tests_with_options(1000, &options);
 
     let hash_test_size_bytes = 1024;
 
-    RingSHA256Metamorphic::run_tests(hash_test_size_bytes);
-    RingSHA384Metamorphic::run_tests(hash_test_size_bytes);
-    RingSHA512Metamorphic::run_tests(hash_test_size_bytes);
-    RingSHA512_256Metamorphic::run_tests(hash_test_size_bytes);
-    Blake3Metamorphic::run_tests(hash_test_size_bytes);
-    WhirlpoolMetamorphic::run_tests(hash_test_size_bytes);
-    TigerMetamorphic::run_tests(hash_test_size_bytes);
-    Sm3Metamorphic::run_tests(hash_test_size_bytes);
-    Skein256Metamorphic::run_tests(hash_test_size_bytes);
-    Skein512Metamorphic::run_tests(hash_test_size_bytes);
-    Skein1024Metamorphic::run_tests(hash_test_size_bytes);
-    Shabal192Metamorphic::run_tests(hash_test_size_bytes);
-    Shabal224Metamorphic::run_tests(hash_test_size_bytes);
-    Shabal256Metamorphic::run_tests(hash_test_size_bytes);
-    Shabal384Metamorphic::run_tests(hash_test_size_bytes);
-    Shabal512Metamorphic::run_tests(hash_test_size_bytes);
-    Ripemd128Metamorphic::run_tests(hash_test_size_bytes);
-    Ripemd160Metamorphic::run_tests(hash_test_size_bytes);
-    Ripemd256Metamorphic::run_tests(hash_test_size_bytes);
-    Ripemd320Metamorphic::run_tests(hash_test_size_bytes);
-    Jh224Metamorphic::run_tests(hash_test_size_bytes);
-    Jh256Metamorphic::run_tests(hash_test_size_bytes);
-    Jh384Metamorphic::run_tests(hash_test_size_bytes);
-    Jh512Metamorphic::run_tests(hash_test_size_bytes);
-    Groestl224Metamorphic::run_tests(hash_test_size_bytes);
-    Groestl256Metamorphic::run_tests(hash_test_size_bytes);
-    Groestl384Metamorphic::run_tests(hash_test_size_bytes);
-    Groestl512Metamorphic::run_tests(hash_test_size_bytes);
-    Fsb160Metamorphic::run_tests(hash_test_size_bytes);
-    Fsb224Metamorphic::run_tests(hash_test_size_bytes);
-    Fsb256Metamorphic::run_tests(hash_test_size_bytes);
-    Fsb384Metamorphic::run_tests(hash_test_size_bytes);
-    Fsb512Metamorphic::run_tests(hash_test_size_bytes);
-    BeltMetamorphic::run_tests(hash_test_size_bytes);
-    AsconMetamorphic::run_tests(hash_test_size_bytes);
-    Blake2s256Metamorphic::run_tests(hash_test_size_bytes);
-    Blake2b512Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_256Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_224Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_256Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_384Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_512Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_512_224Metamorphic::run_tests(hash_test_size_bytes);
-    Sha2_512_256Metamorphic::run_tests(hash_test_size_bytes);
-    Sha3_224Metamorphic::run_tests(hash_test_size_bytes);
-    Sha3_256Metamorphic::run_tests(hash_test_size_bytes);
-    Sha3_384Metamorphic::run_tests(hash_test_size_bytes);
-    Sha3_512Metamorphic::run_tests(hash_test_size_bytes);
+    RingSHA256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingSHA384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingSHA512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingSHA512_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake3Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake3Metamorphic::parallel_paths_test(&options);
+    WhirlpoolMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    TigerMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sm3Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Skein256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Skein512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Skein1024Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shabal192Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shabal224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shabal256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shabal384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shabal512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Ripemd128Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Ripemd160Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Ripemd256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Ripemd320Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Jh224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Jh256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Jh384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Jh512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Groestl224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Groestl256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Groestl384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Groestl512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Fsb160Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Fsb224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Fsb256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Fsb384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Fsb512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    BeltMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    AsconMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake2s256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake2b512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_512_224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha2_512_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha3_224Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha3_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha3_384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Sha3_512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake2b256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Skein512_512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+
+    truncated_variant_test::<Sha2_512Metamorphic, Sha2_512_256Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<Sha2_512Metamorphic, Sha2_512_224Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<Sha2_512Metamorphic, Sha2_384Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<Sha2_256Metamorphic, Sha2_224Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<RingSHA512Metamorphic, RingSHA512_256Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<Blake2b512Metamorphic, Blake2b256Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    truncated_variant_test::<Skein512_512Metamorphic, Skein512Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+
+    blake2b_personalized_test(b"metamorphic", hash_test_size_bytes, &options);
+    blake2b_personalized_test(b"0123456789abcdef", hash_test_size_bytes, &options);
+    blake2b_var_test(20, hash_test_size_bytes, &options);
+    blake2b_var_test(48, hash_test_size_bytes, &options);
+    blake2b_var_distinctness_test(32, 64, hash_test_size_bytes, &options);
+    blake2b_var_distinctness_test(20, 48, hash_test_size_bytes, &options);
+    skein512_output_size_test::<U48>(hash_test_size_bytes, &options);
+
+    Shake128Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Shake256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    CShake128Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    CShake256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    TurboShake128Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    TurboShake256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    KangarooTwelveMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake3XofMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    xof_prefix_test::<Blake3Metamorphic, Blake3XofMetamorphic>(hash_test_size_bytes, &options);
+
+    HmacSha256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    HmacSha512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    HmacSha3_256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingHmacSha256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingHmacSha384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingHmacSha512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake2bMac512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake2sMac256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake3KeyedMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+
+    RingHkdfSha256Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingHkdfSha384Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingHkdfSha512Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Blake3DeriveKeyMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+
+    // Password hashes are slow by design, keep passwords short
+    let password_test_size_bytes = 64;
+    Argon2idMetamorphic::run_tests_with_options(password_test_size_bytes, &options);
+    ScryptMetamorphic::run_tests_with_options(password_test_size_bytes, &options);
+    Pbkdf2Sha256Metamorphic::run_tests_with_options(password_test_size_bytes, &options);
+    BcryptMetamorphic::run_tests_with_options(password_test_size_bytes, &options);
+    RingPbkdf2Sha256Metamorphic::run_tests_with_options(password_test_size_bytes, &options);
+
+    Aes128GcmMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes256GcmMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    ChaCha20Poly1305Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    XChaCha20Poly1305Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingAes128GcmMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingAes256GcmMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    RingChaCha20Poly1305Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    aead_differential_test::<RingAes128GcmMetamorphic, Aes128GcmMetamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    aead_differential_test::<RingAes256GcmMetamorphic, Aes256GcmMetamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+    aead_differential_test::<RingChaCha20Poly1305Metamorphic, ChaCha20Poly1305Metamorphic>(
+        hash_test_size_bytes,
+        &options,
+    );
+
+    // Signing is slower than hashing, keep messages short
+    let signature_test_size_bytes = 256;
+    RingEd25519Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    Ed25519DalekMetamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    RingEcdsaP256Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    EcdsaP256Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    MlDsa44Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    MlDsa65Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    MlDsa87Metamorphic::run_tests_with_options(signature_test_size_bytes, &options);
+    signature_cross_verify_test::<RingEd25519Metamorphic, Ed25519DalekMetamorphic>(
+        signature_test_size_bytes,
+        &options,
+    );
+    signature_cross_verify_test::<Ed25519DalekMetamorphic, RingEd25519Metamorphic>(
+        signature_test_size_bytes,
+        &options,
+    );
+    signature_cross_verify_test::<RingEcdsaP256Metamorphic, EcdsaP256Metamorphic>(
+        signature_test_size_bytes,
+        &options,
+    );
+    signature_cross_verify_test::<EcdsaP256Metamorphic, RingEcdsaP256Metamorphic>(
+        signature_test_size_bytes,
+        &options,
+    );
+    deterministic_signature_differential_test::<RingEd25519Metamorphic, Ed25519DalekMetamorphic>(
+        signature_test_size_bytes,
+        &options,
+    );
+
+    RingX25519Metamorphic::run_tests_with_options(&options);
+    X25519DalekMetamorphic::run_tests_with_options(&options);
+    RingEcdhP256Metamorphic::run_tests_with_options(&options);
+    EcdhP256Metamorphic::run_tests_with_options(&options);
+
+    Aes128EcbMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes256EcbMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes128CbcMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes256CbcMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes128CtrMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    Aes256CtrMetamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+    ChaCha20Metamorphic::run_tests_with_options(hash_test_size_bytes, &options);
+
+    // Measure how many injected faults each hash relation detects
+    hash_mutation_analysis::<Sha2_256Metamorphic>(256, &options);
+    hash_mutation_analysis::<Blake3Metamorphic>(256, &options);
+
+    options
+        .finish()
+        .expect("METAMORPHIC_CHECKPOINT cannot be marked finished");
 }
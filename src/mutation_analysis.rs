@@ -0,0 +1,320 @@
+use crate::{BlockHash, CampaignOptions, HashMetamorphic, MetamorphicRelation};
+use std::marker::PhantomData;
+
+/// Messages whose first byte is below this bound are in the key region of `ConstantOnKeyRegion`
+const KEY_REGION_BOUND: u8 = 0x10;
+
+/// A bug injected in a hash target. The wrapper streams the data of each update call through the
+/// fault, then splits it in blocks of the target block size that the fault may rewrite before the
+/// target absorbs them. The trailing partial block is rewritten before finalization, and the
+/// digest after it.
+pub trait HashFault: 'static {
+    const NAME: &str;
+
+    /// Rewrite the data of an update call, `previous` is the data of the previous call
+    fn inject_update(data: Vec<u8>, _previous: &[u8]) -> Vec<u8> {
+        data
+    }
+
+    /// Rewrite the `index`-th full block before it is compressed
+    fn inject_block(block: Vec<u8>, _index: usize) -> Vec<u8> {
+        block
+    }
+
+    /// Rewrite the trailing bytes that do not fill a whole block
+    fn inject_last_block(block: Vec<u8>) -> Vec<u8> {
+        block
+    }
+
+    /// Rewrite the digest, `first_byte` is the first byte of the message
+    fn inject_output(output: Vec<u8>, _first_byte: Option<u8>) -> Vec<u8> {
+        output
+    }
+}
+
+/// The trailing bytes that do not fill a whole block are never absorbed
+pub struct DropLastPartialBlock {}
+impl HashFault for DropLastPartialBlock {
+    const NAME: &str = "Drop last partial block";
+
+    fn inject_last_block(_block: Vec<u8>) -> Vec<u8> {
+        vec![]
+    }
+}
+
+/// The byte at a fixed offset of every compressed block is ignored
+pub struct IgnoreOneByte {}
+impl HashFault for IgnoreOneByte {
+    const NAME: &str = "Ignore one byte";
+
+    fn inject_block(mut block: Vec<u8>, _index: usize) -> Vec<u8> {
+        if block.len() > 3 {
+            block[3] = 0;
+        }
+        block
+    }
+}
+
+/// The padding does not encode the length, so trailing zero bytes of the last block do not
+/// change the digest
+pub struct WrongLengthEncoding {}
+impl HashFault for WrongLengthEncoding {
+    const NAME: &str = "Wrong length encoding";
+
+    fn inject_last_block(mut block: Vec<u8>) -> Vec<u8> {
+        while block.last() == Some(&0) {
+            block.pop();
+        }
+        block
+    }
+}
+
+/// The update buffer is not refreshed, each update starts with the first byte of the previous one
+pub struct StaleBuffer {}
+impl HashFault for StaleBuffer {
+    const NAME: &str = "Stale buffer";
+
+    fn inject_update(mut data: Vec<u8>, previous: &[u8]) -> Vec<u8> {
+        if let (Some(&stale), false) = (previous.first(), data.is_empty()) {
+            data[0] = stale;
+        }
+        data
+    }
+}
+
+/// A region of the first block, where a key would be, is replaced by a constant
+pub struct ConstantKeyRegion {}
+impl HashFault for ConstantKeyRegion {
+    const NAME: &str = "Constant key region";
+
+    fn inject_block(mut block: Vec<u8>, index: usize) -> Vec<u8> {
+        if index == 0 {
+            let end = block.len().min(32);
+            for byte in block.iter_mut().take(end).skip(16) {
+                *byte = 0;
+            }
+        }
+        block
+    }
+}
+
+/// Every message in a region of the input space, the one starting with a byte below
+/// `KEY_REGION_BOUND`, gets the same constant digest
+pub struct ConstantOnKeyRegion {}
+impl HashFault for ConstantOnKeyRegion {
+    const NAME: &str = "Constant on key region";
+
+    fn inject_output(output: Vec<u8>, first_byte: Option<u8>) -> Vec<u8> {
+        match first_byte {
+            Some(byte) if byte < KEY_REGION_BOUND => vec![0; output.len()],
+            _ => output,
+        }
+    }
+}
+
+/// State of the target along with the data buffered until a block is full
+pub struct MutantState<T: BlockHash> {
+    target: T::State,
+    /// Input of the target, only used as the initial input of its update functions
+    target_input: T::Input,
+    buffer: Vec<u8>,
+    previous: Vec<u8>,
+    blocks: usize,
+    first_byte: Option<u8>,
+}
+
+impl<T: BlockHash> Clone for MutantState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            target_input: self.target_input.clone(),
+            buffer: self.buffer.clone(),
+            previous: self.previous.clone(),
+            blocks: self.blocks,
+            first_byte: self.first_byte,
+        }
+    }
+}
+
+impl<T: BlockHash> MutantState<T> {
+    fn update<F: HashFault>(&mut self, data: &[u8]) {
+        let injected = F::inject_update(data.to_vec(), &self.previous);
+        self.previous = data.to_vec();
+        self.first_byte = self.first_byte.or(data.first().copied());
+        self.buffer.extend_from_slice(&injected);
+        while self.buffer.len() >= T::BLOCK_SIZE {
+            let block: Vec<u8> = self.buffer.drain(..T::BLOCK_SIZE).collect();
+            let block = F::inject_block(block, self.blocks);
+            (self.target, _) = T::hash_update(&self.target, &self.target_input, &block, &[]);
+            self.blocks += 1;
+        }
+    }
+
+    fn finalize<F: HashFault>(self) -> Vec<u8> {
+        let last_block = F::inject_last_block(self.buffer);
+        let (target, input) = T::u8_as_input(&self.target, &self.target_input, last_block);
+        F::inject_output(T::output_as_u8(&T::hash(target, &input)), self.first_byte)
+    }
+}
+
+/// Hash target `T` with the fault `F` injected
+pub struct HashMutant<T: BlockHash, F: HashFault> {
+    _target: PhantomData<T>,
+    _fault: PhantomData<F>,
+}
+
+impl<T: BlockHash, F: HashFault> HashMetamorphic for HashMutant<T, F> {
+    type Input = Vec<u8>;
+    type Output = Vec<u8>;
+    type State = MutantState<T>;
+
+    const LIBNAME: &str = F::NAME;
+
+    fn initial_state() -> Self::State {
+        MutantState {
+            target: T::initial_state(),
+            target_input: T::gen_input(0),
+            buffer: vec![],
+            previous: vec![],
+            blocks: 0,
+            first_byte: None,
+        }
+    }
+
+    fn gen_input(size: usize) -> Self::Input {
+        T::input_as_u8(&T::gen_input(size))
+    }
+
+    fn input_as_u8(input: &Self::Input) -> Vec<u8> {
+        input.to_owned()
+    }
+
+    fn u8_as_input(
+        initial_state: &Self::State,
+        _initial_input: &Self::Input,
+        mutated: Vec<u8>,
+    ) -> (Self::State, Self::Input) {
+        (initial_state.clone(), mutated)
+    }
+
+    fn hash_update(
+        initial_state: &Self::State,
+        _initial_input: &Self::Input,
+        first_part: &[u8],
+        second_part: &[u8],
+    ) -> (Self::State, Self::Input) {
+        let mut state = initial_state.clone();
+        state.update::<F>(first_part);
+        (state, second_part.to_vec())
+    }
+
+    fn output_as_u8(output: &Self::Output) -> Vec<u8> {
+        output.to_owned()
+    }
+
+    fn compare_output(initial_output: &Self::Output, output: &Self::Output) -> bool {
+        initial_output == output
+    }
+
+    fn hash(state: Self::State, input: &Self::Input) -> Self::Output {
+        let mut state = state;
+        state.update::<F>(input);
+        state.finalize::<F>()
+    }
+}
+
+/// Run of a relation on inputs of 1 to `max_size` bytes, returning the number of errors
+type RelationTest = fn(usize, &CampaignOptions) -> usize;
+
+/// Relations of `HashMetamorphic` taking part in the mutation analysis
+fn hash_relations<H: HashMetamorphic>() -> Vec<(String, RelationTest)> {
+    vec![
+        (
+            H::bit_inclusion_relation().name().to_string(),
+            H::bit_inclusion_test_with_options,
+        ),
+        (
+            H::update_hash_relation().name().to_string(),
+            H::update_hash_test_with_options,
+        ),
+        (
+            H::bit_inclusion_update_relation().name().to_string(),
+            H::bit_inclusion_update_test,
+        ),
+        (H::fork_relation().name().to_string(), H::fork_test),
+        (
+            H::alignment_relation().name().to_string(),
+            H::alignment_test,
+        ),
+        (
+            H::short_read_relation().name().to_string(),
+            H::short_read_test,
+        ),
+    ]
+}
+
+/// Number of errors found by each relation on the mutant
+fn run_mutant<T: BlockHash, F: HashFault>(
+    max_size: usize,
+    options: &CampaignOptions,
+) -> (&'static str, Vec<usize>) {
+    (
+        F::NAME,
+        hash_relations::<HashMutant<T, F>>()
+            .iter()
+            .map(|(_, relation)| relation(max_size, options))
+            .collect(),
+    )
+}
+
+/// Run every relation against every injected fault of the target, print which relations kill
+/// which mutants and return the kill rate of each relation
+pub fn hash_mutation_analysis<T: BlockHash>(
+    max_size: usize,
+    options: &CampaignOptions,
+) -> Vec<(String, f64)> {
+    // Mutants are named after their fault, the scope keeps the runs of each target apart
+    let options = &options.clone().with_scope(T::LIBNAME);
+    let results = vec![
+        run_mutant::<T, DropLastPartialBlock>(max_size, options),
+        run_mutant::<T, IgnoreOneByte>(max_size, options),
+        run_mutant::<T, WrongLengthEncoding>(max_size, options),
+        run_mutant::<T, StaleBuffer>(max_size, options),
+        run_mutant::<T, ConstantKeyRegion>(max_size, options),
+        run_mutant::<T, ConstantOnKeyRegion>(max_size, options),
+    ];
+
+    hash_relations::<T>()
+        .into_iter()
+        .enumerate()
+        .map(|(relation_index, (relation_name, _))| {
+            let mut killed = 0;
+            for (fault_name, errors) in &results {
+                let verdict = if errors[relation_index] > 0 {
+                    killed += 1;
+                    "killed"
+                } else {
+                    "survived"
+                };
+                println!(
+                    "[{}] !MUTATION ! {} on {}: {} ({} errors)",
+                    T::LIBNAME,
+                    relation_name,
+                    fault_name,
+                    verdict,
+                    errors[relation_index]
+                );
+            }
+            let kill_rate = killed as f64 / results.len() as f64;
+            println!(
+                "[{}] !MUTATION SUMMARY ! {} kills {}/{} mutants ({:.0}%)",
+                T::LIBNAME,
+                relation_name,
+                killed,
+                results.len(),
+                kill_rate * 100.0
+            );
+            (relation_name, kill_rate)
+        })
+        .collect()
+}
@@ -0,0 +1,260 @@
+use crate::PasswordHashMetamorphic;
+use argon2::{Algorithm, Argon2, Version};
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use pbkdf2::Pbkdf2;
+use scrypt::Scrypt;
+use sha2::Sha256;
+use std::num::NonZeroU32;
+
+/// Size of the derived hashes, when configurable
+const OUTPUT_SIZE: usize = 32;
+
+pub struct Argon2idMetamorphic {}
+impl PasswordHashMetamorphic for Argon2idMetamorphic {
+    type Params = argon2::Params;
+
+    const LIBNAME: &str = "Argon2id";
+
+    const SALTSIZE: usize = 16;
+
+    /// m_cost, t_cost and p_cost
+    const PARAMS_COUNT: usize = 3;
+
+    fn gen_params() -> Self::Params {
+        argon2::Params::new(32, 1, 1, Some(OUTPUT_SIZE)).unwrap()
+    }
+
+    fn mutate_params(params: &Self::Params, idx: usize) -> Self::Params {
+        let (mut m_cost, mut t_cost, mut p_cost) =
+            (params.m_cost(), params.t_cost(), params.p_cost());
+        match idx {
+            0 => m_cost += 8,
+            1 => t_cost += 1,
+            _ => p_cost += 1,
+        }
+        argon2::Params::new(m_cost, t_cost, p_cost, params.output_len()).unwrap()
+    }
+
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8> {
+        let mut output = vec![0u8; OUTPUT_SIZE];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password_into(password, salt, &mut output)
+            .unwrap();
+        output
+    }
+
+    fn hash_password_encoded(
+        password: &[u8],
+        salt: &[u8],
+        params: &Self::Params,
+    ) -> Option<String> {
+        let salt = SaltString::encode_b64(salt).unwrap();
+        let hash = Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password(password, &salt)
+            .unwrap();
+        Some(hash.to_string())
+    }
+
+    fn verify_encoded(password: &[u8], encoded: &str) -> bool {
+        match PasswordHash::new(encoded) {
+            Ok(hash) => Argon2::default().verify_password(password, &hash).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+pub struct ScryptMetamorphic {}
+impl PasswordHashMetamorphic for ScryptMetamorphic {
+    type Params = scrypt::Params;
+
+    const LIBNAME: &str = "Scrypt";
+
+    const SALTSIZE: usize = 16;
+
+    /// log_n, r and p
+    const PARAMS_COUNT: usize = 3;
+
+    fn gen_params() -> Self::Params {
+        scrypt::Params::new(4, 1, 1, OUTPUT_SIZE).unwrap()
+    }
+
+    fn mutate_params(params: &Self::Params, idx: usize) -> Self::Params {
+        let (mut log_n, mut r, mut p) = (params.log_n(), params.r(), params.p());
+        match idx {
+            0 => log_n += 1,
+            1 => r += 1,
+            _ => p += 1,
+        }
+        scrypt::Params::new(log_n, r, p, OUTPUT_SIZE).unwrap()
+    }
+
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8> {
+        let mut output = vec![0u8; OUTPUT_SIZE];
+        scrypt::scrypt(password, salt, params, &mut output).unwrap();
+        output
+    }
+
+    fn hash_password_encoded(
+        password: &[u8],
+        salt: &[u8],
+        params: &Self::Params,
+    ) -> Option<String> {
+        let salt = SaltString::encode_b64(salt).unwrap();
+        let hash = Scrypt
+            .hash_password_customized(password, None, None, *params, &salt)
+            .unwrap();
+        Some(hash.to_string())
+    }
+
+    fn verify_encoded(password: &[u8], encoded: &str) -> bool {
+        match PasswordHash::new(encoded) {
+            Ok(hash) => Scrypt.verify_password(password, &hash).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+pub struct Pbkdf2Sha256Metamorphic {}
+impl PasswordHashMetamorphic for Pbkdf2Sha256Metamorphic {
+    type Params = pbkdf2::Params;
+
+    const LIBNAME: &str = "Pbkdf2 Sha256";
+
+    const SALTSIZE: usize = 16;
+
+    /// rounds
+    const PARAMS_COUNT: usize = 1;
+
+    fn gen_params() -> Self::Params {
+        pbkdf2::Params {
+            rounds: 16,
+            output_length: OUTPUT_SIZE,
+        }
+    }
+
+    fn mutate_params(params: &Self::Params, _idx: usize) -> Self::Params {
+        pbkdf2::Params {
+            rounds: params.rounds + 1,
+            output_length: params.output_length,
+        }
+    }
+
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8> {
+        let mut output = vec![0u8; params.output_length];
+        pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, params.rounds, &mut output);
+        output
+    }
+
+    fn hash_password_encoded(
+        password: &[u8],
+        salt: &[u8],
+        params: &Self::Params,
+    ) -> Option<String> {
+        let salt = SaltString::encode_b64(salt).unwrap();
+        let hash = Pbkdf2
+            .hash_password_customized(password, None, None, *params, &salt)
+            .unwrap();
+        Some(hash.to_string())
+    }
+
+    fn verify_encoded(password: &[u8], encoded: &str) -> bool {
+        match PasswordHash::new(encoded) {
+            Ok(hash) => Pbkdf2.verify_password(password, &hash).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+pub struct BcryptMetamorphic {}
+impl PasswordHashMetamorphic for BcryptMetamorphic {
+    /// Cost
+    type Params = u32;
+
+    const LIBNAME: &str = "Bcrypt";
+
+    const SALTSIZE: usize = 16;
+
+    const PARAMS_COUNT: usize = 1;
+
+    fn gen_params() -> Self::Params {
+        4
+    }
+
+    fn mutate_params(params: &Self::Params, _idx: usize) -> Self::Params {
+        params + 1
+    }
+
+    /// Passwords are NUL terminated and truncated to 72 bytes, as `bcrypt::hash` does
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8> {
+        let terminated = [password, &[0u8]].concat();
+        bcrypt::bcrypt(
+            *params,
+            salt.try_into().unwrap(),
+            &terminated[..terminated.len().min(72)],
+        )
+        .to_vec()
+    }
+
+    fn hash_password_encoded(
+        password: &[u8],
+        salt: &[u8],
+        params: &Self::Params,
+    ) -> Option<String> {
+        let hash = bcrypt::hash_with_salt(password, *params, salt.try_into().unwrap()).unwrap();
+        Some(hash.format_for_version(bcrypt::Version::TwoB))
+    }
+
+    fn verify_encoded(password: &[u8], encoded: &str) -> bool {
+        bcrypt::verify(password, encoded).unwrap_or(false)
+    }
+}
+
+pub struct RingPbkdf2Sha256Metamorphic {}
+impl PasswordHashMetamorphic for RingPbkdf2Sha256Metamorphic {
+    /// Iterations
+    type Params = NonZeroU32;
+
+    const LIBNAME: &str = "Ring Pbkdf2 Sha256";
+
+    const SALTSIZE: usize = 16;
+
+    const PARAMS_COUNT: usize = 1;
+
+    fn gen_params() -> Self::Params {
+        NonZeroU32::new(16).unwrap()
+    }
+
+    fn mutate_params(params: &Self::Params, _idx: usize) -> Self::Params {
+        params.checked_add(1).unwrap()
+    }
+
+    fn hash_password(password: &[u8], salt: &[u8], params: &Self::Params) -> Vec<u8> {
+        let mut output = vec![0u8; OUTPUT_SIZE];
+        ring::pbkdf2::derive(
+            ring::pbkdf2::PBKDF2_HMAC_SHA256,
+            *params,
+            salt,
+            password,
+            &mut output,
+        );
+        output
+    }
+
+    fn verify_raw(
+        password: &[u8],
+        salt: &[u8],
+        params: &Self::Params,
+        expected: &[u8],
+    ) -> Option<bool> {
+        Some(
+            ring::pbkdf2::verify(
+                ring::pbkdf2::PBKDF2_HMAC_SHA256,
+                *params,
+                salt,
+                password,
+                expected,
+            )
+            .is_ok(),
+        )
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{campaign_rng, SignatureMetamorphic};
+use ed25519_dalek::{Signer, Verifier};
+use ml_dsa::{EncodedVerifyingKey, KeyGen, MlDsa44, MlDsa65, MlDsa87, B32};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+
+pub struct RingEd25519Metamorphic {}
+impl SignatureMetamorphic for RingEd25519Metamorphic {
+    /// Seed
+    type SecretKey = [u8; 32];
+
+    const LIBNAME: &str = "Ring Ed25519";
+    const PKSIZE: usize = 32;
+    const SIGSIZE: usize = 64;
+    const DETERMINISTIC: bool = true;
+
+    fn gen_keys() -> (Self::SecretKey, Vec<u8>) {
+        let mut seed = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut campaign_rng(), &mut seed);
+        Self::gen_keys_from_seed(&seed).unwrap()
+    }
+
+    fn gen_keys_from_seed(seed: &[u8; 32]) -> Option<(Self::SecretKey, Vec<u8>)> {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(seed).unwrap();
+        Some((*seed, key_pair.public_key().as_ref().to_vec()))
+    }
+
+    fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8> {
+        Ed25519KeyPair::from_seed_unchecked(sk)
+            .unwrap()
+            .sign(message)
+            .as_ref()
+            .to_vec()
+    }
+
+    fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(&ring::signature::ED25519, pk)
+            .verify(message, signature)
+            .is_ok()
+    }
+}
+
+pub struct Ed25519DalekMetamorphic {}
+impl SignatureMetamorphic for Ed25519DalekMetamorphic {
+    /// Seed
+    type SecretKey = [u8; 32];
+
+    const LIBNAME: &str = "Ed25519 Dalek";
+    const PKSIZE: usize = 32;
+    const SIGSIZE: usize = 64;
+    const DETERMINISTIC: bool = true;
+
+    fn gen_keys() -> (Self::SecretKey, Vec<u8>) {
+        let mut seed = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut campaign_rng(), &mut seed);
+        Self::gen_keys_from_seed(&seed).unwrap()
+    }
+
+    fn gen_keys_from_seed(seed: &[u8; 32]) -> Option<(Self::SecretKey, Vec<u8>)> {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(seed);
+        Some((*seed, signing_key.verifying_key().to_bytes().to_vec()))
+    }
+
+    fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8> {
+        ed25519_dalek::SigningKey::from_bytes(sk)
+            .sign(message)
+            .to_bytes()
+            .to_vec()
+    }
+
+    fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let (Ok(pk), Ok(signature)) = (pk.try_into(), signature.try_into()) else {
+            return false;
+        };
+        match ed25519_dalek::VerifyingKey::from_bytes(pk) {
+            Ok(verifying_key) => verifying_key
+                .verify(message, &ed25519_dalek::Signature::from_bytes(signature))
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+pub struct RingEcdsaP256Metamorphic {}
+impl SignatureMetamorphic for RingEcdsaP256Metamorphic {
+    /// PKCS#8 document
+    type SecretKey = Vec<u8>;
+
+    const LIBNAME: &str = "Ring ECDSA P-256";
+    /// Uncompressed SEC1 point
+    const PKSIZE: usize = 65;
+    /// Fixed size `r || s`
+    const SIGSIZE: usize = 64;
+    const DETERMINISTIC: bool = false;
+
+    fn gen_keys() -> (Self::SecretKey, Vec<u8>) {
+        // Drawn from the OS, ring keys are not replayed from the campaign seed
+        let rng = SystemRandom::new();
+        let pkcs8 =
+            EcdsaKeyPair::generate_pkcs8(&ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+                .unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(
+            &ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            pkcs8.as_ref(),
+            &rng,
+        )
+        .unwrap();
+        (
+            pkcs8.as_ref().to_vec(),
+            key_pair.public_key().as_ref().to_vec(),
+        )
+    }
+
+    fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8> {
+        let rng = SystemRandom::new();
+        EcdsaKeyPair::from_pkcs8(&ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING, sk, &rng)
+            .unwrap()
+            .sign(&rng, message)
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(&ring::signature::ECDSA_P256_SHA256_FIXED, pk)
+            .verify(message, signature)
+            .is_ok()
+    }
+}
+
+pub struct EcdsaP256Metamorphic {}
+impl SignatureMetamorphic for EcdsaP256Metamorphic {
+    /// Secret scalar
+    type SecretKey = Vec<u8>;
+
+    const LIBNAME: &str = "ECDSA P-256 RustCrypto";
+    /// Uncompressed SEC1 point
+    const PKSIZE: usize = 65;
+    /// Fixed size `r || s`
+    const SIGSIZE: usize = 64;
+    /// RFC 6979 nonces
+    const DETERMINISTIC: bool = true;
+
+    fn gen_keys() -> (Self::SecretKey, Vec<u8>) {
+        let signing_key = p256::ecdsa::SigningKey::random(&mut campaign_rng());
+        let pk = signing_key.verifying_key().to_encoded_point(false);
+        (signing_key.to_bytes().to_vec(), pk.as_bytes().to_vec())
+    }
+
+    fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8> {
+        let signature: p256::ecdsa::Signature = p256::ecdsa::SigningKey::from_slice(sk)
+            .unwrap()
+            .sign(message);
+        signature.to_bytes().to_vec()
+    }
+
+    fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let (Ok(verifying_key), Ok(signature)) = (
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(pk),
+            p256::ecdsa::Signature::from_slice(signature),
+        ) else {
+            return false;
+        };
+        verifying_key.verify(message, &signature).is_ok()
+    }
+}
+
+macro_rules! impl_signature_metamorphic_ml_dsa {
+    ($params_type:ty, $struct_name:ident, $libname:expr, $pksize:expr, $sigsize:expr) => {
+        pub struct $struct_name {}
+        impl SignatureMetamorphic for $struct_name {
+            /// Key generation seed `xi`
+            type SecretKey = [u8; 32];
+
+            const LIBNAME: &str = $libname;
+            const PKSIZE: usize = $pksize;
+            const SIGSIZE: usize = $sigsize;
+            /// Hedged signing
+            const DETERMINISTIC: bool = false;
+
+            fn gen_keys() -> (Self::SecretKey, Vec<u8>) {
+                let mut seed = [0u8; 32];
+                rand::RngCore::fill_bytes(&mut campaign_rng(), &mut seed);
+                Self::gen_keys_from_seed(&seed).unwrap()
+            }
+
+            fn gen_keys_from_seed(seed: &[u8; 32]) -> Option<(Self::SecretKey, Vec<u8>)> {
+                let key_pair = <$params_type>::key_gen_internal(&B32::from(*seed));
+                Some((*seed, key_pair.verifying_key().encode().to_vec()))
+            }
+
+            fn sign(sk: &Self::SecretKey, message: &[u8]) -> Vec<u8> {
+                <$params_type>::key_gen_internal(&B32::from(*sk))
+                    .signing_key()
+                    .sign_randomized(message, &[], &mut campaign_rng())
+                    .unwrap()
+                    .encode()
+                    .to_vec()
+            }
+
+            fn verify(pk: &[u8], message: &[u8], signature: &[u8]) -> bool {
+                let (Ok(pk), Ok(signature)) = (
+                    EncodedVerifyingKey::<$params_type>::try_from(pk),
+                    ml_dsa::Signature::<$params_type>::try_from(signature),
+                ) else {
+                    return false;
+                };
+                ml_dsa::VerifyingKey::<$params_type>::decode(&pk).verify_with_context(
+                    message,
+                    &[],
+                    &signature,
+                )
+            }
+        }
+    };
+}
+
+impl_signature_metamorphic_ml_dsa!(MlDsa44, MlDsa44Metamorphic, "ML-DSA-44", 1312, 2420);
+impl_signature_metamorphic_ml_dsa!(MlDsa65, MlDsa65Metamorphic, "ML-DSA-65", 1952, 3309);
+impl_signature_metamorphic_ml_dsa!(MlDsa87, MlDsa87Metamorphic, "ML-DSA-87", 2592, 4627);
@@ -0,0 +1,117 @@
+use crate::{campaign_rng, XofMetamorphic};
+use blake3::Hasher;
+use k12::{KangarooTwelve, KangarooTwelveCore};
+use rand;
+use rand::RngCore;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{
+    CShake128, CShake128Core, CShake256, CShake256Core, Shake128, Shake256, TurboShake128,
+    TurboShake128Core, TurboShake256, TurboShake256Core,
+};
+
+/// Customization string used by customizable XOFs
+const CUSTOMIZATION: &[u8] = b"metamorphic-testing-rs";
+/// Default TurboSHAKE domain separation byte
+const TURBOSHAKE_DOMAIN_SEPARATION: u8 = 0x1f;
+
+macro_rules! impl_xof_metamorphic {
+    ($xof_type:ty, $init:expr, $test_struct_name:ident, $libname:literal) => {
+        pub struct $test_struct_name {}
+        impl XofMetamorphic for $test_struct_name {
+            type Input = Vec<u8>;
+            type State = $xof_type;
+
+            const LIBNAME: &str = $libname;
+
+            fn gen_input(size: usize) -> Self::Input {
+                let mut data = vec![0u8; size];
+                campaign_rng().fill_bytes(&mut data);
+                data
+            }
+
+            fn input_as_u8(input: &Self::Input) -> Vec<u8> {
+                input.to_owned()
+            }
+
+            fn xof(state: Self::State, input: &Self::Input, reads: &[usize]) -> Vec<u8> {
+                let mut hasher = state;
+
+                hasher.update(input);
+
+                let mut reader = hasher.finalize_xof();
+                let mut output = vec![0u8; reads.iter().sum()];
+                let mut offset = 0;
+                for read in reads {
+                    reader.read(&mut output[offset..offset + read]);
+                    offset += read;
+                }
+                output
+            }
+
+            fn initial_state() -> Self::State {
+                $init
+            }
+
+            fn u8_as_input(
+                intial_state: &Self::State,
+                _initial_input: &Self::Input,
+                mutated: Vec<u8>,
+            ) -> (Self::State, Self::Input) {
+                (intial_state.clone(), mutated)
+            }
+        }
+    };
+}
+
+impl_xof_metamorphic! {Shake128, Shake128::default(), Shake128Metamorphic, "Shake128"}
+impl_xof_metamorphic! {Shake256, Shake256::default(), Shake256Metamorphic, "Shake256"}
+impl_xof_metamorphic! {CShake128, CShake128::from_core(CShake128Core::new(CUSTOMIZATION)), CShake128Metamorphic, "CShake128"}
+impl_xof_metamorphic! {CShake256, CShake256::from_core(CShake256Core::new(CUSTOMIZATION)), CShake256Metamorphic, "CShake256"}
+impl_xof_metamorphic! {TurboShake128, TurboShake128::from_core(TurboShake128Core::new(TURBOSHAKE_DOMAIN_SEPARATION)), TurboShake128Metamorphic, "TurboShake128"}
+impl_xof_metamorphic! {TurboShake256, TurboShake256::from_core(TurboShake256Core::new(TURBOSHAKE_DOMAIN_SEPARATION)), TurboShake256Metamorphic, "TurboShake256"}
+impl_xof_metamorphic! {KangarooTwelve<'static>, KangarooTwelve::from_core(KangarooTwelveCore::new(CUSTOMIZATION)), KangarooTwelveMetamorphic, "KangarooTwelve"}
+
+pub struct Blake3XofMetamorphic {}
+impl XofMetamorphic for Blake3XofMetamorphic {
+    type Input = Vec<u8>;
+    type State = Hasher;
+
+    const LIBNAME: &str = "Blake3 XOF";
+
+    fn gen_input(size: usize) -> Self::Input {
+        let mut data = vec![0u8; size];
+        campaign_rng().fill_bytes(&mut data);
+        data
+    }
+
+    fn input_as_u8(input: &Self::Input) -> Vec<u8> {
+        input.to_owned()
+    }
+
+    fn xof(state: Self::State, input: &Self::Input, reads: &[usize]) -> Vec<u8> {
+        let mut hasher = state;
+
+        hasher.update(input);
+
+        let mut reader = hasher.finalize_xof();
+        let mut output = vec![0u8; reads.iter().sum()];
+        let mut offset = 0;
+        for read in reads {
+            reader.fill(&mut output[offset..offset + read]);
+            offset += read;
+        }
+        output
+    }
+
+    fn initial_state() -> Self::State {
+        Self::State::new()
+    }
+
+    fn u8_as_input(
+        intial_state: &Self::State,
+        _initial_input: &Self::Input,
+        mutated: Vec<u8>,
+    ) -> (Self::State, Self::Input) {
+        (intial_state.clone(), mutated)
+    }
+}
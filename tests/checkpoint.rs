@@ -0,0 +1,93 @@
+//! A campaign resumed from a checkpoint cut in the middle of a size reports what the
+//! uninterrupted campaign reports
+use metamorphic_testing_rs::{
+    campaign_rng, outputs_differ, BitInclusionMutator, CampaignOptions, MetamorphicTarget, Relation,
+};
+use rand::RngCore;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Input size in bytes: its bit positions span several chunks of mutations
+const SIZE: usize = 1000;
+
+/// Drops the bytes below 128, so that flipping a low bit of one of them is missed: the errors
+/// depend on the generated input
+fn run(options: CampaignOptions) -> usize {
+    MetamorphicTarget::new(
+        |size| {
+            let mut data = vec![0u8; size];
+            campaign_rng().fill_bytes(&mut data);
+            data
+        },
+        || (),
+        |_state, input: &Vec<u8>| -> Vec<u8> {
+            input.iter().copied().filter(|byte| *byte >= 128).collect()
+        },
+    )
+    .with_options(options)
+    .run_relation(
+        SIZE,
+        SIZE,
+        "High bytes filter",
+        &Relation::new(
+            "Bit Inclusion",
+            BitInclusionMutator::new(
+                |input: &Vec<u8>| input.clone(),
+                |_state: &(), _input: &Vec<u8>, mutated| ((), mutated),
+            ),
+            outputs_differ,
+        ),
+    )
+}
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "metamorphic-testing-rs-checkpoint-{}-{}",
+        name,
+        std::process::id()
+    ))
+}
+
+fn sorted_lines(path: &Path) -> Vec<String> {
+    let mut lines: Vec<String> = fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    lines.sort();
+    lines
+}
+
+#[test]
+fn resumed_campaign_matches_uninterrupted_one() {
+    let full = checkpoint_path("full");
+    let resumed = checkpoint_path("resumed");
+    let _ = fs::remove_file(&full);
+
+    let errors = run(CampaignOptions::new()
+        .with_seed(42)
+        .with_checkpoint(&full)
+        .unwrap());
+    assert!(errors > 0);
+    assert_eq!(run(CampaignOptions::new().with_seed(42)), errors);
+
+    // Interrupted in the middle of the size: the first chunks are recorded, the next line is cut
+    let content = fs::read_to_string(&full).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert!(lines.len() > 3);
+    let kept = lines.len() / 2;
+    let mut cut = lines[..kept].join("\n") + "\n";
+    cut.push_str(&lines[kept][..lines[kept].len() / 2]);
+    fs::write(&resumed, cut).unwrap();
+
+    // The seed recorded in the checkpoint replays the generated input, not the one of the options
+    let resumed_errors = run(CampaignOptions::new()
+        .with_seed(7)
+        .with_checkpoint(&resumed)
+        .unwrap());
+    assert_eq!(resumed_errors, errors);
+    assert_eq!(sorted_lines(&resumed), sorted_lines(&full));
+
+    fs::remove_file(&full).unwrap();
+    fs::remove_file(&resumed).unwrap();
+}